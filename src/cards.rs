@@ -1,9 +1,8 @@
-use rand::seq::SliceRandom;
-use rand::thread_rng;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use strum::{EnumIter, IntoEnumIterator};
 
-#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
 pub enum Rank {
     Ace = 1,
@@ -42,7 +41,19 @@ impl Display for Rank {
     }
 }
 
-#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+impl FromStr for Rank {
+    type Err = ();
+
+    /// Accepts "A", "2" - "10", "J", "Q", "K", and "T" for 10 (in either case)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "T" => Ok(Self::R10),
+            s => Self::iter().find(|rank| rank.to_string() == s).ok_or(()),
+        }
+    }
+}
+
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum Suit {
     Hearts = 0,
@@ -58,6 +69,15 @@ impl Suit {
             Self::Spades | Self::Clubs => false,
         }
     }
+    /// Single-letter (ASCII) name
+    pub fn letter(self) -> char {
+        match self {
+            Self::Hearts => 'H',
+            Self::Spades => 'S',
+            Self::Diamonds => 'D',
+            Self::Clubs => 'C',
+        }
+    }
     pub fn from_index(index: usize) -> Option<Self> {
         use Suit::*;
         match index {
@@ -82,7 +102,20 @@ impl Display for Suit {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+impl FromStr for Suit {
+    type Err = ();
+
+    /// Accepts a suit symbol or letter (in either case)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::iter()
+            .find(|suit| {
+                s == suit.to_string() || s.eq_ignore_ascii_case(&suit.letter().to_string())
+            })
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Card {
     pub suit: Suit,
     pub rank: Rank,
@@ -94,6 +127,20 @@ impl Display for Card {
     }
 }
 
+impl FromStr for Card {
+    type Err = ();
+
+    /// Accepts a rank then a suit, e.g.: "10♥", "TH", "Th"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let suit = chars.next_back().ok_or(())?;
+        Ok(Self {
+            suit: suit.to_string().parse()?,
+            rank: chars.as_str().parse()?,
+        })
+    }
+}
+
 impl Card {
     #[allow(dead_code)]
     pub fn new(suit: Suit, rank: Rank) -> Self {
@@ -110,14 +157,38 @@ impl Card {
         cards
     }
 
-    pub fn shuffled_deck() -> Vec<Self> {
-        let mut rng = thread_rng();
+    /// A deck shuffled from `seed`: the same seed always gives the same deck.
+    /// (Don't change this algorithm: that would change every shared seed's deal)
+    pub fn seeded_deck(seed: u64) -> Vec<Self> {
+        let mut rng = SplitMix64(seed);
         let mut deck = Self::ordered_deck();
-        deck.shuffle(&mut rng);
+        // Fisher-Yates shuffle
+        for i in (1..deck.len()).rev() {
+            deck.swap(i, rng.below(i + 1));
+        }
         deck
     }
 }
 
+/// A small pseudo-random number generator with a fixed, documented algorithm
+/// (unlike `rand`'s generators, whose output may change between versions)
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,25 +217,40 @@ mod tests {
     }
 
     #[test]
-    fn test_shuffled_deck() {
-        const PRINT: bool = true;
-        let cards = Card::shuffled_deck();
-        if PRINT {
-            print!("Black: ");
-            for card in &cards {
-                if !card.suit.is_red() {
-                    print!("{card} ");
-                }
-            }
-            println!();
-            print!("Red:   ");
-            for card in &cards {
-                if card.suit.is_red() {
-                    print!("{card} ");
-                }
-            }
-            println!();
+    fn test_card_from_str() {
+        let ten_of_hearts = Card::new(Suit::Hearts, Rank::R10);
+        assert_eq!("10♥".parse(), Ok(ten_of_hearts));
+        assert_eq!("TH".parse(), Ok(ten_of_hearts));
+        assert_eq!("Th".parse(), Ok(ten_of_hearts));
+        assert_eq!("qs".parse(), Ok(Card::new(Suit::Spades, Rank::Queen)));
+
+        for card in Card::ordered_deck() {
+            assert_eq!(card.to_string().parse(), Ok(card));
         }
-        assert_eq!(cards.len(), 52);
+
+        for s in ["", "H", "10", "1H", "11H", "10X", "10♥ "] {
+            assert_eq!(s.parse::<Card>(), Err(()), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_seeded_deck() {
+        use Rank::*;
+        use Suit::*;
+        let cards = Card::seeded_deck(1);
+        assert_eq!(
+            cards[..3],
+            [
+                Card::new(Clubs, R9),
+                Card::new(Clubs, R7),
+                Card::new(Diamonds, Queen)
+            ]
+        );
+        assert_eq!(cards, Card::seeded_deck(1));
+        assert_ne!(cards, Card::seeded_deck(2));
+
+        let mut sorted = cards;
+        sorted.sort_by_key(|card| (card.suit as u8, card.rank));
+        assert_eq!(sorted, Card::ordered_deck());
     }
 }
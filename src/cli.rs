@@ -0,0 +1,312 @@
+//! Command-line arguments.
+
+use crate::draw::Theme;
+use crate::game_logic::{Move, Scoring};
+use crate::game_state::Rules;
+use crate::replay::Replay;
+use crate::solver::{self, Budget, SolveResult};
+use crate::stats::{Bankroll, Stats};
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = r#"Usage: solitext [OPTIONS] [COMMAND]
+
+Commands:
+  (none)          Play (starts a new game directly if --draw or --seed is given)
+  solve           Search for a win on a deal; prints it as a replay file
+  replay <FILE>   Play back a replay file, then continue the game
+  stats           Print win/loss statistics
+  help            Print this help
+  version         Print the version
+
+Options:
+  --draw <N>          Cards drawn from the stock per hit (usually 1 or 3)
+  --seed <N>          Deal from this seed (random if not given)
+  --theme <NAME>      Color theme: classic, dark, mono
+  --ascii             Use only ASCII characters
+  --scoring <NAME>    Scoring: off, standard (default), vegas
+  --bankroll          Keep a cumulative bankroll for Vegas scoring
+  --passes <N>        Passes allowed through the stock: a number, or unlimited
+                      (default; Vegas scoring has its own limit)
+  --empty-column <kings|any>
+                      Cards allowed into an empty column (default: kings)
+  --no-foundation-return
+                      Don't allow cards back from the foundations
+  --manual-flip       Turn up uncovered cards with Enter, instead of automatically
+  --auto-play         Move cards to the foundations automatically, when that's safe
+  --resume            Continue the saved game
+  --input <FILE>      Play with the key presses in a file, instead of the keyboard
+  --max-time <SECS>   [solve] Time limit for the search (default: 10)
+  --delay <MS>        [replay] Delay between moves (default: 300)
+
+Exit status for solve: 0 = solved, 1 = unsolvable, 3 = unknown (search limit)
+"#;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Command {
+    Play,
+    Solve,
+    Replay(PathBuf),
+    Stats,
+    Help,
+    Version,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    /// Cards drawn per hit, if given
+    pub draw_count: Option<usize>,
+    pub seed: Option<u64>,
+    pub theme: Theme,
+    pub ascii: bool,
+    pub scoring: Scoring,
+    pub bankroll: bool,
+    /// Rules for new games (the draw count is chosen with each new game)
+    pub rules: Rules,
+    /// Make safe moves to the foundations automatically
+    pub auto_play: bool,
+    pub resume: bool,
+    /// A file of key presses to play with, instead of the keyboard
+    pub input: Option<PathBuf>,
+    pub max_time: Duration,
+    pub delay: Duration,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            command: Command::Play,
+            draw_count: None,
+            seed: None,
+            theme: Theme::default(),
+            ascii: false,
+            scoring: Scoring::default(),
+            bankroll: false,
+            rules: Rules::default(),
+            auto_play: false,
+            resume: false,
+            input: None,
+            max_time: Budget::default().max_time,
+            delay: Duration::from_millis(300),
+        }
+    }
+}
+
+/// Parse arguments (excluding the program name)
+/// Returns: the parsed arguments, or a description of the first error
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    let mut command = None;
+
+    while let Some(arg) = args.next() {
+        // Accept both "--option value" and "--option=value"
+        let (option, inline_value) = match arg.split_once('=') {
+            Some((option, value)) if arg.starts_with("--") => (option.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline_value
+                .map(str::to_string)
+                .or_else(|| args.next())
+                .ok_or(format!("{option} needs a value"))
+        };
+        let invalid = |value: &str| format!("invalid value for {option}: {value}");
+
+        match option.as_str() {
+            "--draw" => {
+                let value = value()?;
+                parsed.draw_count =
+                    Some(Rules::parse_draw_count(&value).map_err(|_| invalid(&value))?);
+            }
+            "--seed" => {
+                let value = value()?;
+                parsed.seed = Some(value.parse().map_err(|_| invalid(&value))?);
+            }
+            "--theme" => {
+                let value = value()?;
+                parsed.theme = value
+                    .parse()
+                    .map_err(|_| format!("{} (themes: {})", invalid(&value), Theme::NAMES))?;
+            }
+            "--ascii" => parsed.ascii = true,
+            "--scoring" => {
+                let value = value()?;
+                parsed.scoring = value
+                    .parse()
+                    .map_err(|_| format!("{} (scoring: {})", invalid(&value), Scoring::NAMES))?;
+            }
+            "--bankroll" => parsed.bankroll = true,
+            "--passes" => {
+                let value = value()?;
+                parsed.rules.pass_limit =
+                    Rules::parse_pass_limit(&value).map_err(|_| invalid(&value))?;
+            }
+            "--empty-column" => {
+                let value = value()?;
+                parsed.rules.empty_column = value.parse().map_err(|_| invalid(&value))?;
+            }
+            "--no-foundation-return" => parsed.rules.foundation_to_tableau = false,
+            "--manual-flip" => parsed.rules.auto_flip = false,
+            "--auto-play" => parsed.auto_play = true,
+            "--resume" => parsed.resume = true,
+            "--input" => parsed.input = Some(value()?.into()),
+            "--max-time" => {
+                let value = value()?;
+                parsed.max_time = Duration::from_secs(value.parse().map_err(|_| invalid(&value))?);
+            }
+            "--delay" => {
+                let value = value()?;
+                parsed.delay = Duration::from_millis(value.parse().map_err(|_| invalid(&value))?);
+            }
+            "-h" | "--help" => command = Some(Command::Help),
+            "-V" | "--version" => command = Some(Command::Version),
+            _ if option.starts_with('-') => return Err(format!("unknown option: {option}")),
+            _ if command.is_some() => return Err(format!("unexpected argument: {arg}")),
+            "solve" => command = Some(Command::Solve),
+            "replay" => {
+                let path = args.next().ok_or("replay needs a file")?;
+                command = Some(Command::Replay(path.into()));
+            }
+            "stats" => command = Some(Command::Stats),
+            "help" => command = Some(Command::Help),
+            "version" => command = Some(Command::Version),
+            _ => return Err(format!("unknown command: {arg}")),
+        }
+    }
+
+    parsed.command = command.unwrap_or(Command::Play);
+    Ok(parsed)
+}
+
+/// Run the `solve` command, printing the result as a replay file
+/// Returns: the exit status
+pub fn run_solve(args: &Args) -> i32 {
+    let replay = Replay {
+        seed: args.seed.unwrap_or_else(rand::random),
+        rules: Rules {
+            draw_count: args.draw_count.unwrap_or(1),
+            ..args.rules
+        },
+        moves: vec![],
+    };
+    let budget = Budget {
+        max_time: args.max_time,
+        ..Default::default()
+    };
+
+    match solver::solve(&replay.initial_state(), budget) {
+        SolveResult::Solved(moves) => {
+            // Face-up flips are automatic when replayed (unless the rules say otherwise)
+            let moves: Vec<Move> = moves
+                .into_iter()
+                .filter(|mv| !(replay.rules.auto_flip && matches!(mv, Move::Flip { .. })))
+                .collect();
+            println!("# solved in {} moves", moves.len());
+            print!("{}", Replay { moves, ..replay });
+            0
+        }
+        SolveResult::Unsolvable => {
+            println!("# unsolvable");
+            print!("{replay}");
+            1
+        }
+        SolveResult::Unknown => {
+            println!("# unknown: no win found within the search limits");
+            print!("{replay}");
+            3
+        }
+    }
+}
+
+/// Run the `stats` command
+/// Returns: the exit status
+pub fn run_stats() -> i32 {
+    match Stats::load().and_then(|stats| Ok((stats, Bankroll::load()?))) {
+        Ok((stats, Bankroll(bankroll))) => {
+            println!("{}", stats.table());
+            println!("Vegas bankroll: {}", Scoring::Vegas.format(bankroll));
+            0
+        }
+        Err(error) => {
+            eprintln!("solitext: {error}");
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::EmptyColumn;
+
+    fn parse_str(args: &str) -> Result<Args, String> {
+        parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_str(""), Ok(Args::default()));
+
+        let args = parse_str("--draw 3 --seed=42 --theme mono --ascii").unwrap();
+        assert_eq!(args.command, Command::Play);
+        assert_eq!(args.draw_count, Some(3));
+        assert_eq!(args.seed, Some(42));
+        assert_eq!(args.theme, Theme::Mono);
+        assert!(args.ascii);
+
+        assert!(parse_str("--resume").unwrap().resume);
+        assert_eq!(
+            parse_str("--input keys.txt").unwrap().input,
+            Some("keys.txt".into())
+        );
+        assert!(parse_str("--auto-play").unwrap().auto_play);
+        assert_eq!(parse_str("--scoring off").unwrap().scoring, Scoring::Off);
+        assert!(parse_str("--scoring vegas --bankroll").unwrap().bankroll);
+        assert_eq!(parse_str("--passes 3").unwrap().rules.pass_limit, Some(3));
+        assert_eq!(
+            parse_str("--passes unlimited").unwrap().rules.pass_limit,
+            None
+        );
+        let args = parse_str("--draw 2 --empty-column any --no-foundation-return --manual-flip");
+        assert_eq!(
+            args.unwrap().rules,
+            Rules {
+                empty_column: EmptyColumn::AnyCard,
+                foundation_to_tableau: false,
+                auto_flip: false,
+                ..Default::default()
+            }
+        );
+
+        let args = parse_str("solve --seed 7 --max-time 2").unwrap();
+        assert_eq!(args.command, Command::Solve);
+        assert_eq!(args.max_time, Duration::from_secs(2));
+
+        let args = parse_str("--delay 0 replay game.txt").unwrap();
+        assert_eq!(args.command, Command::Replay("game.txt".into()));
+        assert_eq!(args.delay, Duration::ZERO);
+
+        assert_eq!(parse_str("--help").unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_str("--draw 0"),
+            Err("invalid value for --draw: 0".to_string())
+        );
+        assert_eq!(parse_str("--seed"), Err("--seed needs a value".to_string()));
+        assert_eq!(
+            parse_str("--frobnicate"),
+            Err("unknown option: --frobnicate".to_string())
+        );
+        assert_eq!(parse_str("replay"), Err("replay needs a file".to_string()));
+        assert_eq!(
+            parse_str("solve solve"),
+            Err("unexpected argument: solve".to_string())
+        );
+        assert_eq!(parse_str("win"), Err("unknown command: win".to_string()));
+    }
+}
@@ -0,0 +1,65 @@
+//! A game clock that can be paused (e.g.: while the menu is open).
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Clock {
+    /// Time counted before the latest start
+    counted: Duration,
+    /// When the clock was last started, if it's running
+    started: Option<Instant>,
+}
+
+impl Clock {
+    /// A running clock, starting from `elapsed`
+    pub fn starting_at(elapsed: Duration) -> Self {
+        Self {
+            counted: elapsed,
+            started: Some(Instant::now()),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.counted
+            + self
+                .started
+                .map_or(Duration::ZERO, |started| started.elapsed())
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(started) = self.started.take() {
+            self.counted += started.elapsed();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        self.started.get_or_insert_with(Instant::now);
+    }
+
+    /// Time until the displayed (whole) seconds next change
+    pub fn until_next_second(&self) -> Duration {
+        Duration::from_secs(1) - Duration::from_nanos(self.elapsed().subsec_nanos().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock() {
+        let mut clock = Clock::starting_at(Duration::from_secs(60));
+        clock.pause();
+        let paused = clock.elapsed();
+        assert!(paused >= Duration::from_secs(60));
+        assert!(paused < Duration::from_secs(61));
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(clock.elapsed(), paused);
+
+        clock.resume();
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(clock.elapsed() >= paused + Duration::from_millis(10));
+
+        assert_eq!(Clock::default().until_next_second(), Duration::from_secs(1));
+    }
+}
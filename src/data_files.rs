@@ -0,0 +1,72 @@
+//! Files kept between runs, in `$XDG_DATA_HOME/solitext/`.
+
+use std::path::PathBuf;
+use std::{fs, io};
+
+#[cfg(test)]
+thread_local! {
+    /// A directory to use instead, for tests on this thread
+    pub static TEST_DIR: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
+/// The directory for solitext's data files, following the XDG base directory spec
+pub fn dir() -> Option<PathBuf> {
+    #[cfg(test)]
+    if let Some(dir) = TEST_DIR.with(|dir| dir.borrow().clone()) {
+        return Some(dir);
+    }
+    let xdg_data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute());
+    let data_home = xdg_data_home.or_else(|| {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+    })?;
+    Some(data_home.join("solitext"))
+}
+
+fn path(name: &str) -> Result<PathBuf, String> {
+    Ok(dir().ok_or("no data directory")?.join(name))
+}
+
+/// Returns: the file's text, or None if it doesn't exist
+pub fn read(name: &str) -> Result<Option<String>, String> {
+    let path = path(name)?;
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(format!("{}: {error}", path.display())),
+    }
+}
+
+/// Read and parse a file
+/// Returns: the parsed value, or None if the file doesn't exist
+pub fn load<T>(name: &str) -> Result<Option<T>, String>
+where
+    T: std::str::FromStr<Err = String>,
+{
+    let path = path(name)?;
+    read(name)?
+        .map(|text| text.parse())
+        .transpose()
+        .map_err(|error| format!("{}: {error}", path.display()))
+}
+
+pub fn write(name: &str, text: &str) -> Result<(), String> {
+    let path = path(name)?;
+    let error = |error: io::Error| format!("{}: {error}", path.display());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    fs::write(&path, text).map_err(error)
+}
+
+/// Remove a file, if it exists
+pub fn remove(name: &str) -> Result<(), String> {
+    let path = path(name)?;
+    match fs::remove_file(&path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => {
+            Err(format!("{}: {error}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
@@ -1,33 +1,116 @@
+mod backend;
 mod card;
 mod card_column;
+mod color;
 mod common;
 mod deck;
 mod foundation;
 mod game_state;
+mod grid;
 mod info;
+mod layout;
+mod theme;
+
+pub use backend::{Backend, TermionBackend};
+pub use color::Color;
+pub use grid::{Cell, Grid};
+pub use layout::Layout;
+pub use theme::Theme;
 
 use crate::selection::Selection;
-use std::io::{stdout, Stdout};
-use termion::raw::{IntoRawMode, RawTerminal};
+use std::time::Duration;
 
-pub struct Draw {
-    stdout: RawTerminal<Stdout>,
-    pub cursor: Selection,
-    pub selected: Option<Selection>,
+pub struct Draw<B: Backend = TermionBackend> {
+    backend: B,
+    /// Where the board goes on the screen
+    layout: Layout,
+    pub(crate) cursor: Selection,
+    pub(crate) selected: Option<Selection>,
     pub context_help_message: String,
     pub debug_message: String,
     pub debug_mode: bool,
+    /// The current game's seed, for display
+    pub game_seed: Option<u64>,
+    /// The current game's score, for display (if it's scored)
+    pub score: Option<String>,
+    /// The current game's time and number of moves, for display
+    pub game_time: Duration,
+    pub move_count: usize,
+    pub theme: Theme,
+    /// Use only ASCII characters (no suit symbols, arrows, or blocks)
+    pub ascii: bool,
 }
 
 impl Draw {
-    pub fn new() -> Self {
+    /// Draw to the terminal (which this puts in raw mode)
+    pub fn new(theme: Theme, ascii: bool) -> Self {
+        Self::with_backend(TermionBackend::new(), theme, ascii)
+    }
+}
+
+impl<B: Backend> Draw<B> {
+    pub fn with_backend(backend: B, theme: Theme, ascii: bool) -> Self {
         Self {
-            stdout: stdout().into_raw_mode().unwrap(),
+            layout: Layout::new(backend.size()),
+            backend,
             cursor: Selection::Deck,
             selected: None,
             context_help_message: "".to_string(),
             debug_message: "".to_string(),
             debug_mode: false,
+            game_seed: None,
+            score: None,
+            game_time: Duration::ZERO,
+            move_count: 0,
+            theme,
+            ascii,
         }
     }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Lay out the board again, for the current screen size.
+    /// (Redraw the screen afterwards)
+    pub fn resize(&mut self) {
+        self.backend.update_size();
+        self.layout = Layout::new(self.backend.size());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::game_state::GameState;
+
+    #[test]
+    fn test_resize() {
+        let game_state = GameState::init(Card::ordered_deck());
+        let mut draw = Draw::with_backend(Grid::new(80, 30), Theme::default(), true);
+        draw.display_game_state(&game_state);
+        // (The board is centered, below any rows the columns can't use)
+        assert!(draw
+            .backend()
+            .row_text(3)
+            .unwrap()
+            .starts_with("    Solitext "));
+
+        draw.backend_mut().set_size(60, 10);
+        draw.resize();
+        draw.display_game_state(&game_state);
+        assert_eq!(
+            draw.backend().text(),
+            "Terminal too small\nNeed 72x13, have 60x10"
+        );
+    }
 }
@@ -0,0 +1,203 @@
+//! Where drawing goes: a real terminal (via termion), or a `Grid` in memory.
+
+use super::color::Color;
+use super::grid::Grid;
+use std::fmt::Write as _;
+use std::io::{stdout, Stdout, Write};
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::{clear, color, cursor};
+
+/// A screen of character cells, addressed by 1-based column and row.
+/// Drawing makes up a frame, which `flush` shows.
+pub trait Backend {
+    /// Blank the whole screen, using the current background color
+    fn clear(&mut self);
+    fn set_colors(&mut self, foreground: Color, background: Color);
+    /// Write `text` starting at `col`, `row`
+    fn draw_text(&mut self, col: usize, row: usize, text: &str);
+    /// Make everything drawn so far visible
+    fn flush(&mut self);
+    /// Take over the screen (e.g.: hide the cursor)
+    fn set_up(&mut self);
+    /// Give the screen back, cleared, with the cursor at the top left
+    fn restore(&mut self);
+    /// The screen size, as (columns, rows)
+    fn size(&self) -> (usize, usize);
+    /// Catch up with any change in the screen size (which blanks the frame)
+    fn update_size(&mut self);
+}
+
+/// A terminal in raw mode, on stdout.
+/// Frames are double-buffered: `flush` writes only the cells that changed since the last one.
+pub struct TermionBackend {
+    stdout: RawTerminal<Stdout>,
+    /// The frame being drawn
+    frame: Grid,
+    /// The frame on the terminal now (None if that's unknown)
+    shown: Option<Grid>,
+    /// The terminal's current colors (None if they're unknown)
+    colors: Option<(Color, Color)>,
+}
+
+impl TermionBackend {
+    pub fn new() -> Self {
+        let (width, height) = Self::terminal_size();
+        Self {
+            stdout: stdout().into_raw_mode().unwrap(),
+            frame: Grid::new(width, height),
+            shown: None,
+            colors: None,
+        }
+    }
+
+    fn terminal_size() -> (usize, usize) {
+        let (width, height) = termion::terminal_size().unwrap_or((80, 24));
+        (width.into(), height.into())
+    }
+}
+
+impl Default for TermionBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for TermionBackend {
+    fn clear(&mut self) {
+        self.frame.clear();
+    }
+
+    fn set_colors(&mut self, foreground: Color, background: Color) {
+        self.frame.set_colors(foreground, background);
+    }
+
+    fn draw_text(&mut self, col: usize, row: usize, text: &str) {
+        self.frame.draw_text(col, row, text);
+    }
+
+    fn flush(&mut self) {
+        let changes = frame_changes(self.shown.as_ref(), &self.frame, &mut self.colors);
+        self.shown = Some(self.frame.clone());
+        self.stdout.write_all(changes.as_bytes()).unwrap();
+        self.stdout.flush().unwrap();
+    }
+
+    fn set_up(&mut self) {
+        write!(self.stdout, "{}", cursor::Hide).unwrap();
+        self.shown = None;
+    }
+
+    fn restore(&mut self) {
+        write!(
+            self.stdout,
+            "{}{}{}{}{}",
+            color::Fg(color::Reset),
+            color::Bg(color::Reset),
+            clear::All,
+            cursor::Goto(1, 1),
+            cursor::Show,
+        )
+        .unwrap();
+        self.stdout.flush().unwrap();
+        self.shown = None;
+        self.colors = None;
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (self.frame.width(), self.frame.height())
+    }
+
+    fn update_size(&mut self) {
+        let (width, height) = Self::terminal_size();
+        if (width, height) != self.size() {
+            self.frame = Grid::new(width, height);
+            // (The terminal may have moved or wrapped what was on it)
+            self.shown = None;
+        }
+    }
+}
+
+/// Escape codes and text that change a terminal from the `shown` frame (if known) to `frame`.
+/// `colors`: the terminal's current colors (if known), which this updates
+fn frame_changes(
+    shown: Option<&Grid>,
+    frame: &Grid,
+    colors: &mut Option<(Color, Color)>,
+) -> String {
+    let mut changes = String::new();
+    // Where the terminal's cursor is, if that's known
+    let mut position = None;
+    for row in 1..=frame.height() {
+        for col in 1..=frame.width() {
+            let cell = frame.cell(col, row).expect("cell should be on the grid");
+            let shown_cell = shown.and_then(|shown| shown.cell(col, row));
+            if shown_cell.is_some_and(|shown_cell| shown_cell.looks_same(cell)) {
+                continue;
+            }
+
+            if position != Some((col, row)) {
+                let goto = cursor::Goto(
+                    u16::try_from(col).expect("column should fit in a u16"),
+                    u16::try_from(row).expect("row should fit in a u16"),
+                );
+                write!(changes, "{goto}").unwrap();
+            }
+            // (A space's foreground color doesn't show)
+            let colors_match = colors.is_some_and(|(foreground, background)| {
+                background == cell.background
+                    && (cell.symbol == ' ' || foreground == cell.foreground)
+            });
+            if !colors_match {
+                let (foreground, background) = (cell.foreground, cell.background);
+                write!(
+                    changes,
+                    "{}{}",
+                    color::Fg(foreground),
+                    color::Bg(background)
+                )
+                .unwrap();
+                *colors = Some((foreground, background));
+            }
+            changes.push(cell.symbol);
+            // (After the last column, the cursor's position depends on the terminal)
+            position = (col < frame.width()).then_some((col + 1, row));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_changes() {
+        let mut frame = Grid::new(4, 2);
+        frame.set_colors(Color::Red, Color::Black);
+        frame.draw_text(1, 1, "ab");
+        let mut colors = None;
+
+        // Everything, the first time
+        let all = frame_changes(None, &frame, &mut colors);
+        let red = format!("{}{}", color::Fg(Color::Red), color::Bg(Color::Black));
+        assert_eq!(
+            all,
+            format!("{}{red}ab  {}    ", cursor::Goto(1, 1), cursor::Goto(1, 2))
+        );
+        assert_eq!(colors, Some((Color::Red, Color::Black)));
+
+        // Nothing, if nothing changed
+        let shown = frame.clone();
+        assert_eq!(frame_changes(Some(&shown), &frame, &mut colors), "");
+
+        // Just the changed cells, with the colors set only when they change
+        frame.draw_text(2, 1, "c");
+        frame.set_colors(Color::Blue, Color::Black);
+        frame.draw_text(4, 2, "d");
+        let blue = format!("{}{}", color::Fg(Color::Blue), color::Bg(Color::Black));
+        assert_eq!(
+            frame_changes(Some(&shown), &frame, &mut colors),
+            format!("{}c{}{blue}d", cursor::Goto(2, 1), cursor::Goto(4, 2))
+        );
+    }
+}
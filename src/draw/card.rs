@@ -1,11 +1,11 @@
 //! Draws a card.
 
-use super::Draw;
-use crate::cards::Card;
+use super::color::Color::*;
+use super::{Backend, Draw};
+use crate::cards::{Card, Suit};
 use crate::game_state::CardState;
-use termion::color::*;
 
-impl Draw {
+impl<B: Backend> Draw<B> {
     pub(crate) fn display_card(
         &mut self,
         card: Card,
@@ -13,14 +13,15 @@ impl Draw {
         col: usize,
         row: usize,
     ) {
+        let palette = self.theme.palette();
         let text = match card_state {
             CardState::FaceUp => {
                 if card.suit.is_red() {
-                    self.set_colors(Red, White);
+                    self.set_color_pair(palette.red_card);
                 } else {
-                    self.set_colors(Black, White);
+                    self.set_color_pair(palette.black_card);
                 }
-                card.to_string()
+                self.card_text(card)
             }
             CardState::FaceDown => {
                 if self.debug_mode {
@@ -29,9 +30,9 @@ impl Draw {
                     } else {
                         self.set_colors(LightBlack, Black);
                     }
-                    card.to_string()
+                    self.card_text(card)
                 } else {
-                    self.set_colors(LightGreen, LightBlack);
+                    self.set_color_pair(palette.card_back);
                     "st".to_string()
                 }
             }
@@ -39,4 +40,20 @@ impl Draw {
 
         self.draw_text(col, row, text.as_str());
     }
+
+    pub(crate) fn card_text(&self, card: Card) -> String {
+        if self.ascii {
+            format!("{}{}", card.rank, card.suit.letter())
+        } else {
+            card.to_string()
+        }
+    }
+
+    pub(crate) fn suit_text(&self, suit: Suit) -> String {
+        if self.ascii {
+            suit.letter().to_string()
+        } else {
+            suit.to_string()
+        }
+    }
 }
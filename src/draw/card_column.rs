@@ -1,11 +1,10 @@
 //! Draws a column of cards in the tableau.
 
-use super::Draw;
+use super::{Backend, Draw};
 use crate::cards::Card;
 use crate::game_state::{CardState, GameState};
 use crate::selection::Selection;
 use std::cmp::min;
-use termion::color::*;
 
 enum CardColumnScroll {
     AtMaxRow,
@@ -17,7 +16,7 @@ struct ScrolledColumn {
     at_edge: Option<CardColumnScroll>,
 }
 
-impl Draw {
+impl<B: Backend> Draw<B> {
     pub(super) const COLUMNS_INIT_COL: usize = 8;
     pub(super) const COLUMNS_INIT_ROW: usize = 2;
     pub(super) const COLUMNS_COL_STEP: usize = 5;
@@ -29,18 +28,22 @@ impl Draw {
             if let Some(ScrolledColumn {
                 visible_cards,
                 at_edge,
-            }) = Self::scrolled_column(&column.0, self.selection_count(index))
-            {
+            }) = Self::scrolled_column(
+                &column.0,
+                self.selection_count(index),
+                self.layout.column_cards(),
+            ) {
+                let scroll_arrows = self.theme.palette().scroll_arrows;
                 if !matches!(at_edge, Some(CardColumnScroll::AtMaxRow)) {
-                    self.set_colors(White, Green);
-                    self.draw_text(col - 1, row, "↑  ↑");
+                    self.set_color_pair(scroll_arrows);
+                    self.draw_text(col - 1, row, self.symbol("↑  ↑", "^  ^"));
                 }
                 if !matches!(at_edge, Some(CardColumnScroll::AtMinRow)) {
-                    self.set_colors(White, Green);
+                    self.set_color_pair(scroll_arrows);
                     self.draw_text(
                         col - 1,
                         row - 1 + (visible_cards.len() * Self::COLUMNS_ROW_STEP),
-                        "↓  ↓",
+                        self.symbol("↓  ↓", "v  v"),
                     );
                 }
 
@@ -58,17 +61,18 @@ impl Draw {
         }
     }
 
-    const COLUMN_MAX_VISIBLE_CARDS: usize = 7;
     /// Scrolled offset in card column + position info, or None if not scrolled
+    /// `visible_cards`: how many cards of the column fit on the screen
     fn scrolled_column_offset(
         cards: usize,
         selected: usize,
+        visible_cards: usize,
     ) -> Option<(usize, Option<CardColumnScroll>)> {
-        if cards <= Self::COLUMN_MAX_VISIBLE_CARDS {
+        if cards <= visible_cards {
             return None;
         }
 
-        let max_offset = cards - Self::COLUMN_MAX_VISIBLE_CARDS;
+        let max_offset = cards - visible_cards;
         let unselected = cards - min(selected, cards);
         let offset = min(unselected, max_offset);
 
@@ -82,13 +86,17 @@ impl Draw {
     }
 
     /// A scrolled card column's visible cards, or None if not scrolled
-    fn scrolled_column(cards: &Vec<(Card, CardState)>, selected: usize) -> Option<ScrolledColumn> {
-        Self::scrolled_column_offset(cards.len(), selected).map(|(offset, at_edge)| {
-            ScrolledColumn {
-                visible_cards: cards[offset..offset + Self::COLUMN_MAX_VISIBLE_CARDS].into(),
+    fn scrolled_column(
+        cards: &[(Card, CardState)],
+        selected: usize,
+        visible_cards: usize,
+    ) -> Option<ScrolledColumn> {
+        Self::scrolled_column_offset(cards.len(), selected, visible_cards).map(
+            |(offset, at_edge)| ScrolledColumn {
+                visible_cards: cards[offset..offset + visible_cards].into(),
                 at_edge,
-            }
-        })
+            },
+        )
     }
 
     /// A column's active selection count; 0 if not selected.
@@ -114,7 +122,9 @@ impl Draw {
         card_count: usize,
     ) {
         let length = game_state.columns[index].0.len();
-        let (scroll, _) = Self::scrolled_column_offset(length, card_count).unwrap_or((0, None));
+        let visible_cards = self.layout.column_cards();
+        let (scroll, _) =
+            Self::scrolled_column_offset(length, card_count, visible_cards).unwrap_or((0, None));
 
         let upper = Self::COLUMNS_INIT_ROW
             + Self::COLUMNS_ROW_STEP
@@ -128,7 +138,7 @@ impl Draw {
         // Don't draw past the end of the column
         let upper = min(
             upper,
-            Self::COLUMNS_INIT_ROW + Self::COLUMNS_ROW_STEP * Self::COLUMN_MAX_VISIBLE_CARDS,
+            Self::COLUMNS_INIT_ROW + Self::COLUMNS_ROW_STEP * visible_cards,
         );
 
         for row in lower..upper {
@@ -137,3 +147,48 @@ impl Draw {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::draw::{Grid, Layout, Theme};
+
+    #[test]
+    fn test_scrolled_column_offset() {
+        let offset = Draw::<Grid>::scrolled_column_offset;
+        // A column that fits doesn't scroll
+        assert!(offset(10, 1, 10).is_none());
+        assert!(offset(10, 1, 18).is_none());
+        assert!(matches!(
+            offset(10, 1, 7),
+            Some((3, Some(CardColumnScroll::AtMinRow)))
+        ));
+        assert!(matches!(
+            offset(10, 10, 7),
+            Some((0, Some(CardColumnScroll::AtMaxRow)))
+        ));
+        assert!(matches!(offset(10, 8, 7), Some((2, None))));
+    }
+
+    #[test]
+    fn test_long_column() {
+        let mut game_state = GameState::init(Card::ordered_deck());
+        let moved: Vec<_> = game_state.columns[6].0.drain(..).collect();
+        game_state.columns[0].0.extend(moved);
+        // The symbol where the column's scroll arrows go: on its first row, or its 8th card's row
+        let arrows = |(width, height)| {
+            let mut draw = Draw::with_backend(Grid::new(width, height), Theme::Classic, true);
+            draw.display_game_state(&game_state);
+            [2, 9].map(|row| {
+                let (col, row) = draw.layout().position(7, row);
+                draw.backend().cell(col, row).unwrap().symbol
+            })
+        };
+
+        // The column scrolls on the smallest screen...
+        assert_eq!(arrows(Layout::MIN_SIZE), ['^', ' ']);
+        // ...but fits on a taller one
+        assert_eq!(arrows((80, 24)), [' ', ' ']);
+    }
+}
@@ -0,0 +1,29 @@
+//! Terminal colors.
+
+use std::fmt;
+use termion::color::AnsiValue;
+
+/// The standard 16 terminal colors (the ones used here)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Color {
+    Black = 0,
+    Red = 1,
+    Green = 2,
+    Blue = 4,
+    White = 7,
+    LightBlack = 8,
+    LightRed = 9,
+    LightGreen = 10,
+    LightYellow = 11,
+    LightBlue = 12,
+    LightWhite = 15,
+}
+
+impl termion::color::Color for Color {
+    fn write_fg(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        AnsiValue(*self as u8).write_fg(f)
+    }
+    fn write_bg(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        AnsiValue(*self as u8).write_bg(f)
+    }
+}
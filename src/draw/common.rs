@@ -1,91 +1,87 @@
 //! Common drawing code.
 
-use super::Draw;
-use std::io::Write;
-use termion::{clear, color, cursor};
+use super::color::Color;
+use super::{Backend, Draw, Layout};
+use std::cmp::max;
 
-impl Draw {
+impl<B: Backend> Draw<B> {
     pub(crate) fn clear_screen(&mut self) {
-        writeln!(self.stdout, "{}", clear::All,).unwrap();
+        self.backend.clear();
     }
 
-    pub(crate) fn default_bg() -> impl color::Color {
-        color::Black
+    pub(crate) fn default_bg(&self) -> Color {
+        self.theme.palette().background
     }
-    pub(crate) fn default_fg() -> impl color::Color {
-        color::LightWhite
+    pub(crate) fn default_fg(&self) -> Color {
+        self.theme.palette().foreground
     }
 
-    pub(crate) fn set_colors(
-        &mut self,
-        foreground: impl color::Color,
-        background: impl color::Color,
-    ) {
-        writeln!(
-            self.stdout,
-            "{}{}",
-            color::Fg(foreground),
-            color::Bg(background),
-        )
-        .unwrap();
+    /// The text for a symbol, depending on whether only ASCII is allowed
+    pub(crate) fn symbol(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        if self.ascii {
+            ascii
+        } else {
+            unicode
+        }
+    }
+
+    pub(crate) fn set_color_pair(&mut self, (foreground, background): (Color, Color)) {
+        self.set_colors(foreground, background);
+    }
+
+    pub(crate) fn set_colors(&mut self, foreground: Color, background: Color) {
+        self.backend.set_colors(foreground, background);
     }
 
     pub(crate) fn draw_box(&mut self, col1: usize, row1: usize, col2: usize, row2: usize) {
         use std::cmp::{max, min};
         for col in min(col1, col2)..=max(col1, col2) {
             for row in min(row1, row2)..=max(row1, row2) {
-                self.draw_text(col, row, "█");
+                self.draw_text(col, row, self.symbol("█", "#"));
             }
         }
     }
 
+    /// Draw text at a board position
     pub fn draw_text(&mut self, col: usize, row: usize, text: &str) {
-        let col = u16::try_from(col).expect("column should fit in a u16");
-        let row = u16::try_from(row).expect("row should fit in a u16");
-
-        writeln!(self.stdout, "{}{}", cursor::Goto(col, row), text).unwrap();
+        let (col, row) = self.layout.position(col, row);
+        self.backend.draw_text(col, row, text);
     }
 
     pub fn set_up_terminal(&mut self) {
-        writeln!(
-            self.stdout,
-            "{}{}{}{}{}",
-            color::Fg(Self::default_fg()),
-            color::Bg(Self::default_bg()),
-            clear::All,
-            cursor::Goto(1, 1),
-            cursor::Hide,
-        )
-        .unwrap();
-        self.stdout.flush().unwrap();
+        self.backend.set_up();
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.clear_screen();
+        self.flush();
     }
 
     pub fn restore_terminal(&mut self) {
-        writeln!(
-            self.stdout,
-            "{}{}{}{}{}",
-            color::Fg(color::Reset),
-            color::Bg(color::Reset),
-            clear::All,
-            cursor::Goto(1, 1),
-            cursor::Show,
-        )
-        .unwrap();
-        self.stdout.flush().unwrap();
+        self.backend.restore();
+    }
+
+    /// Show the frame (or, if the board doesn't fit, a notice about that instead)
+    pub(crate) fn flush(&mut self) {
+        if !self.layout.fits() {
+            self.display_too_small();
+        }
+        self.backend.flush();
     }
 
-    fn centered_box_corners(width: usize, height: usize) -> (usize, usize, usize, usize) {
-        const CENTER: (usize, usize) = (26, 5);
+    fn centered_box_corners(
+        width: usize,
+        height: usize,
+        center_row: usize,
+    ) -> (usize, usize, usize, usize) {
         (
-            CENTER.0 - width / 2,
-            CENTER.1 - height / 2,
-            CENTER.0 + width / 2,
-            CENTER.1 + height / 2,
+            Layout::CENTER_COL - width / 2,
+            center_row - height / 2,
+            Layout::CENTER_COL + width / 2,
+            center_row + height / 2,
         )
     }
 
-    fn draw_centered_box(&mut self, width: usize, height: usize) {
-        let (col1, row1, col2, row2) = Self::centered_box_corners(width, height);
+    fn draw_centered_box(&mut self, width: usize, height: usize, center_row: usize) {
+        let (col1, row1, col2, row2) = Self::centered_box_corners(width, height, center_row);
         self.draw_box(col1, row1, col2, row2);
     }
 
@@ -93,13 +89,16 @@ impl Draw {
         let height = lines.split('\n').count();
 
         const WIDTH: usize = 38;
-        self.set_colors(color::LightBlue, Self::default_bg());
-        self.draw_centered_box(WIDTH, height + 2);
-        self.set_colors(color::White, Self::default_bg());
-        self.draw_centered_box(WIDTH - 2, height);
+        // (Tall boxes move down, so that they start below the top row)
+        let center_row = max(5, (height + 2) / 2 + 1);
+        let palette = self.theme.palette();
+        self.set_colors(palette.text_box_border, self.default_bg());
+        self.draw_centered_box(WIDTH, height + 2, center_row);
+        self.set_colors(palette.text_box.1, self.default_bg());
+        self.draw_centered_box(WIDTH - 2, height, center_row);
 
-        self.set_colors(color::LightBlack, color::White);
-        let (col, mut row, _, _) = Self::centered_box_corners(WIDTH - 2, height);
+        self.set_color_pair(palette.text_box);
+        let (col, mut row, _, _) = Self::centered_box_corners(WIDTH - 2, height, center_row);
 
         for line in lines.split('\n') {
             self.draw_text(col, row, line);
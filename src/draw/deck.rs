@@ -1,20 +1,18 @@
 //! Draws the stock & wastepile decks.
 
-use super::Draw;
-use crate::game_state::{CardState, GameMode, GameState};
-use termion::color;
+use super::{Backend, Draw};
+use crate::game_state::{CardState, GameState};
 
-impl Draw {
+impl<B: Backend> Draw<B> {
     pub(super) fn draw_deck_selection_cursor(&mut self, col: usize, row: usize) {
-        self.draw_text(col + 2, row, "◂");
-        self.draw_text(col - 2, row, "▸");
+        self.draw_text(col + 2, row, self.symbol("◂", "<"));
+        self.draw_text(col - 2, row, self.symbol("▸", ">"));
     }
 
-    fn max_visible_cards(game_mode: GameMode) -> usize {
-        match game_mode {
-            GameMode::DrawOne => 1,
-            GameMode::DrawThree => Self::DECK_DRAWN_MAX_DISPLAY_CARDS,
-        }
+    fn max_visible_cards(game_state: &GameState) -> usize {
+        game_state
+            .draw_count()
+            .min(Self::DECK_DRAWN_MAX_DISPLAY_CARDS)
     }
 
     pub(super) const DECK_INIT_COL: usize = 2;
@@ -23,16 +21,22 @@ impl Draw {
     const DECK_ROW_STEP: usize = 1;
     const DECK_DRAWN_MAX_DISPLAY_CARDS: usize = 3;
     pub(super) fn display_deck(&mut self, game_state: &GameState) {
-        use color::*;
         let (col, mut row) = (Self::DECK_INIT_COL, Self::DECK_INIT_ROW);
         if let Some(card) = game_state.deck.last() {
             self.display_card(*card, CardState::FaceDown, col, row);
         } else {
-            self.set_colors(Green, LightBlack);
+            self.set_color_pair(self.theme.palette().empty_stock);
             self.draw_text(col, row, " O ");
         };
 
-        let max_cards = Self::max_visible_cards(game_state.game_mode);
+        // Show the pass number under the stock, if passes are limited
+        if let Some(pass_limit) = game_state.rules.pass_limit {
+            self.set_colors(self.theme.palette().label, self.default_bg());
+            let text = format!("{}/{pass_limit}", game_state.pass());
+            self.draw_text(col, row + 1, &text);
+        }
+
+        let max_cards = Self::max_visible_cards(game_state);
 
         // display up to `max_cards` cards from the top of the drawn pile
         row += Self::DECK_DRAWN_STEP;
@@ -43,7 +47,7 @@ impl Draw {
     }
 
     pub(super) fn deck_selection_cursor_row(game_state: &GameState) -> Option<usize> {
-        let max_cards = Self::max_visible_cards(game_state.game_mode);
+        let max_cards = Self::max_visible_cards(game_state);
 
         let displayed_cards = game_state.deck_drawn.iter().take(max_cards).count();
         if displayed_cards > 0 {
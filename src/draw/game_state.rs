@@ -1,32 +1,33 @@
 //! Draws the full game state with selections.
 
-use super::Draw;
+use super::{Backend, Draw};
 use crate::game_state::GameState;
 use crate::selection::Selection;
-use termion::color;
 
-impl Draw {
+impl<B: Backend> Draw<B> {
     pub fn display_game_state(&mut self, game_state: &GameState) {
         self.clear_screen();
-        self.set_colors(Self::default_fg(), Self::default_bg());
+        self.set_colors(self.default_fg(), self.default_bg());
 
         self.display_info();
         self.display_deck(game_state);
         self.display_columns(game_state);
         self.display_piles(game_state);
 
-        self.set_colors(color::Blue, Self::default_bg());
+        let palette = self.theme.palette();
+        self.set_colors(palette.collection_cursor, self.default_bg());
         self.display_collection_selection_cursor();
 
-        self.set_colors(Self::default_fg(), color::LightGreen);
+        self.set_color_pair(palette.card_cursor);
         self.display_card_selection_cursor(self.cursor, game_state);
 
-        self.set_colors(Self::default_fg(), color::LightYellow);
+        self.set_color_pair(palette.selected);
         if let Some(selected) = self.selected {
             self.display_card_selection_cursor(selected, game_state);
         }
 
-        self.set_colors(Self::default_fg(), Self::default_bg());
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
     }
 
     fn selection_col(selection: Selection) -> usize {
@@ -39,10 +40,14 @@ impl Draw {
         }
     }
 
-    pub(super) const CURSOR_ROW: usize = 10;
+    /// The row below the columns (after a gap)
+    pub(super) fn cursor_row(&self) -> usize {
+        Self::COLUMNS_INIT_ROW + Self::COLUMNS_ROW_STEP * self.layout.column_cards() + 1
+    }
+
     fn display_collection_selection_cursor(&mut self) {
         let col = Self::selection_col(self.cursor);
-        self.draw_text(col, Self::CURSOR_ROW, "█↑█");
+        self.draw_text(col, self.cursor_row(), self.symbol("█↑█", "#^#"));
     }
 
     fn display_card_selection_cursor(&mut self, selection: Selection, game_state: &GameState) {
@@ -61,3 +66,49 @@ impl Draw {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::draw::{Grid, Layout, Theme};
+    use crate::game_logic;
+
+    /// A grid just big enough for the board
+    fn board_grid() -> Grid {
+        let (width, height) = Layout::MIN_SIZE;
+        Grid::new(width, height)
+    }
+
+    #[test]
+    fn test_display_game_state() {
+        let mut game_state = GameState::init(Card::ordered_deck());
+        game_logic::face_up_on_columns(&mut game_state);
+        let mut draw = Draw::with_backend(board_grid(), Theme::Classic, true);
+        draw.game_seed = Some(5);
+        draw.cursor = Selection::Column {
+            index: 1,
+            card_count: 1,
+        };
+        draw.display_game_state(&game_state);
+
+        let screen = r#"Solitext                       h: Help  Esc: Menu   Moves: 0  Time: 0:00
+ st    KC   st   st   st   st   st   st        H_
+           [JC ] st   st   st   st   st
+                 8C   st   st   st   st        S_
+                      4C   st   st   st
+                           QD   st   st        D_
+                                6D   st
+                                     QS        C_
+
+            #^#
+ Space: Select/Move cards      Seed: 5"#;
+        assert_eq!(draw.backend().text(), screen);
+
+        let palette = Theme::Classic.palette();
+        let cursor = draw.backend().cell(12, 3).unwrap();
+        assert_eq!((cursor.foreground, cursor.background), palette.card_cursor);
+        let jack = draw.backend().cell(13, 3).unwrap();
+        assert_eq!((jack.foreground, jack.background), palette.black_card);
+    }
+}
@@ -0,0 +1,188 @@
+//! An in-memory screen, e.g.: for checking exactly what gets drawn in tests.
+
+use super::backend::Backend;
+use super::color::Color;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub foreground: Color,
+    pub background: Color,
+}
+
+impl Cell {
+    fn blank((foreground, background): (Color, Color)) -> Self {
+        Self {
+            symbol: ' ',
+            foreground,
+            background,
+        }
+    }
+
+    /// Whether the cells would look the same on a terminal
+    /// (a space's foreground color doesn't show)
+    pub fn looks_same(self, other: Self) -> bool {
+        if self.symbol == ' ' && other.symbol == ' ' {
+            self.background == other.background
+        } else {
+            self == other
+        }
+    }
+}
+
+/// A grid of cells that records drawing, like a terminal of a fixed size
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    /// Rows of cells, from the top
+    cells: Vec<Cell>,
+    colors: (Color, Color),
+    pub cursor_visible: bool,
+}
+
+impl Grid {
+    /// Colors before any are set (or after a reset)
+    const DEFAULT_COLORS: (Color, Color) = (Color::White, Color::Black);
+
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::blank(Self::DEFAULT_COLORS); width * height],
+            colors: Self::DEFAULT_COLORS,
+            cursor_visible: true,
+        }
+    }
+
+    /// Change the size, blanking every cell (like a terminal resize, for `update_size`)
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        *self = Self {
+            colors: self.colors,
+            cursor_visible: self.cursor_visible,
+            ..Self::new(width, height)
+        };
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns: the cell at a 1-based `col` and `row`, if it's on the grid
+    pub fn cell(&self, col: usize, row: usize) -> Option<Cell> {
+        self.index(col, row).map(|index| self.cells[index])
+    }
+
+    /// The text of a 1-based row, without trailing spaces (or None if the row is off the grid)
+    pub fn row_text(&self, row: usize) -> Option<String> {
+        let start = self.index(1, row)?;
+        let text: String = self.cells[start..start + self.width]
+            .iter()
+            .map(|cell| cell.symbol)
+            .collect();
+        Some(text.trim_end().to_string())
+    }
+
+    /// The text of every row (without trailing spaces or blank rows at the bottom)
+    pub fn text(&self) -> String {
+        let rows: Vec<String> = (1..=self.height)
+            .filter_map(|row| self.row_text(row))
+            .collect();
+        rows.join("\n").trim_end().to_string()
+    }
+
+    fn index(&self, col: usize, row: usize) -> Option<usize> {
+        let (col, row) = (col.checked_sub(1)?, row.checked_sub(1)?);
+        (col < self.width && row < self.height).then_some(row * self.width + col)
+    }
+}
+
+impl Backend for Grid {
+    fn clear(&mut self) {
+        self.cells.fill(Cell::blank(self.colors));
+    }
+
+    fn set_colors(&mut self, foreground: Color, background: Color) {
+        self.colors = (foreground, background);
+    }
+
+    /// (Text past the right edge is cut off, as on a terminal without line wrap)
+    fn draw_text(&mut self, col: usize, row: usize, text: &str) {
+        let (foreground, background) = self.colors;
+        for (offset, symbol) in text.chars().enumerate() {
+            if let Some(index) = self.index(col + offset, row) {
+                self.cells[index] = Cell {
+                    symbol,
+                    foreground,
+                    background,
+                };
+            }
+        }
+    }
+
+    fn flush(&mut self) {}
+
+    fn set_up(&mut self) {
+        self.cursor_visible = false;
+    }
+
+    fn restore(&mut self) {
+        self.colors = Self::DEFAULT_COLORS;
+        self.clear();
+        self.cursor_visible = true;
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// (A grid's size doesn't change)
+    fn update_size(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let mut a = Grid::new(10, 3);
+        a.set_colors(Color::Red, Color::Blue);
+        a.draw_text(8, 2, "abcdef");
+        a.draw_text(1, 4, "not drawn");
+        assert_eq!(a.text(), "\n       abc");
+        assert_eq!(
+            a.cell(8, 2),
+            Some(Cell {
+                symbol: 'a',
+                foreground: Color::Red,
+                background: Color::Blue,
+            })
+        );
+        assert_eq!(a.cell(11, 2), None);
+        assert_eq!(a.row_text(2).as_deref(), Some("       abc"));
+        assert_eq!(a.row_text(0), None);
+        assert_eq!(a.row_text(4), None);
+
+        a.set_colors(Color::Green, Color::Black);
+        a.clear();
+        assert_eq!(a.text(), "");
+        assert_eq!(a.cell(1, 1).unwrap().background, Color::Black);
+    }
+
+    #[test]
+    fn test_looks_same() {
+        let cell = |symbol, foreground| Cell {
+            symbol,
+            foreground,
+            background: Color::Black,
+        };
+        assert!(cell(' ', Color::Red).looks_same(cell(' ', Color::Blue)));
+        assert!(!cell('x', Color::Red).looks_same(cell('x', Color::Blue)));
+        assert!(!cell(' ', Color::Red).looks_same(cell('x', Color::Red)));
+    }
+}
@@ -1,35 +1,68 @@
 //! Draws info for the user: text labels, instructions, menus, messages, etc.
 
-use super::Draw;
-use crate::game_state::GameState;
-use std::io::Write;
+use super::color::Color;
+use super::{Backend, Draw, Layout};
+use crate::game_logic::Scoring;
+use crate::game_state::{GameState, Rules};
+use crate::stats::format_time;
 use std::{thread, time};
-use termion::color;
 
-impl Draw {
+impl<B: Backend> Draw<B> {
     pub(super) fn display_info(&mut self) {
-        use color::*;
-
-        self.set_colors(LightYellow, Self::default_bg());
+        let palette = self.theme.palette();
+        self.set_colors(palette.title, self.default_bg());
         self.draw_text(1, 1, "Solitext");
+        if let Some(score) = self.score.clone() {
+            self.draw_text(12, 1, &score);
+        }
 
-        self.set_colors(LightBlack, Self::default_bg());
+        self.set_colors(palette.label, self.default_bg());
         self.draw_text(32, 1, "h: Help  Esc: Menu");
-        self.draw_text(2, Self::CURSOR_ROW + 1, "Space: Select/Move cards");
+        self.draw_clock();
+        self.draw_text(2, self.cursor_row() + 1, "Space: Select/Move cards");
+        if let Some(seed) = self.game_seed {
+            self.draw_text(32, self.cursor_row() + 1, format!("Seed: {seed}").as_str());
+        }
         self.draw_text(
             2,
-            Self::CURSOR_ROW + 2,
+            self.cursor_row() + 2,
             self.context_help_message.clone().as_str(),
         );
         if self.debug_mode {
-            self.draw_text(2, Self::CURSOR_ROW + 3, self.debug_message.clone().as_str());
+            self.draw_text(
+                2,
+                self.cursor_row() + 3,
+                self.debug_message.clone().as_str(),
+            );
         }
     }
 
+    fn clock_text(&self) -> String {
+        format!(
+            "Moves: {}  Time: {}",
+            self.move_count,
+            format_time(self.game_time)
+        )
+    }
+
+    fn draw_clock(&mut self) {
+        self.set_colors(self.theme.palette().label, self.default_bg());
+        // (At the right edge of the board)
+        let text = self.clock_text();
+        self.draw_text(Layout::BOARD_WIDTH + 1 - text.len(), 1, &text);
+    }
+
+    /// Redraw just the game clock and move count
+    pub fn display_clock(&mut self) {
+        self.draw_clock();
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
+    }
+
     fn display_victory_message(&mut self) {
-        const CENTER: (usize, usize) = (26, 5);
+        const CENTER: (usize, usize) = (Layout::CENTER_COL, 5);
         const WIDTH_VAL: usize = 3;
-        fn draw_box(s: &mut Draw, size: usize) {
+        fn draw_box<B: Backend>(s: &mut Draw<B>, size: usize) {
             s.draw_box(
                 CENTER.0 - WIDTH_VAL - size,
                 CENTER.1 - size,
@@ -37,29 +70,36 @@ impl Draw {
                 CENTER.1 + size,
             );
         }
-        fn pause() {
+        /// Show what's drawn so far, for a moment
+        fn pause<B: Backend>(s: &mut Draw<B>) {
+            s.flush();
             thread::sleep(time::Duration::from_millis(300));
         }
 
-        self.set_colors(color::Blue, Self::default_bg());
+        self.set_colors(Color::Blue, self.default_bg());
         draw_box(self, 3);
-        pause();
-        self.set_colors(color::Green, Self::default_bg());
+        pause(self);
+        self.set_colors(Color::Green, self.default_bg());
         draw_box(self, 2);
-        pause();
-        self.set_colors(color::Red, Self::default_bg());
+        pause(self);
+        self.set_colors(Color::Red, self.default_bg());
         draw_box(self, 1);
-        pause();
+        pause(self);
 
-        self.set_colors(color::LightYellow, color::LightBlue);
+        self.set_colors(Color::LightYellow, Color::LightBlue);
         self.draw_text(CENTER.0 - 3, CENTER.1, "YOU WIN");
-        pause();
-        pause();
-        self.set_colors(Self::default_fg(), Self::default_bg());
+        pause(self);
+        pause(self);
+        self.set_colors(self.default_fg(), self.default_bg());
+        if let Some(score) = self.score.clone() {
+            self.draw_text(CENTER.0 - score.len() / 2, CENTER.1 + 6, &score);
+        }
+        let clock = self.clock_text();
+        self.draw_text(CENTER.0 - clock.len() / 2, CENTER.1 + 7, &clock);
         self.draw_text(CENTER.0 - 8, CENTER.1 + 4, "Play again? (y/n)");
     }
 
-    pub fn display_victory(&mut self, game_state: &mut GameState) {
+    pub fn display_victory(&mut self, game_state: &GameState) {
         self.clear_screen();
         //just display cards
         self.display_deck(game_state);
@@ -68,43 +108,169 @@ impl Draw {
 
         self.display_victory_message();
 
-        self.set_colors(Self::default_fg(), Self::default_bg());
-        self.stdout.flush().unwrap();
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
     }
 
-    pub fn display_start_screen(&mut self) {
+    /// `counts`: whether leaving the game counts it as a loss
+    pub fn display_stalemate(&mut self, game_state: &GameState, counts: bool) {
         self.clear_screen();
-        self.set_colors(color::LightYellow, Self::default_bg());
-        self.draw_text(16, 1, "Solitext    ♥ ♠ ♦ ♣");
+        //just display cards
+        self.display_deck(game_state);
+        self.display_columns(game_state);
+        self.display_piles(game_state);
 
-        let lines = r#"1: New Game (Draw One)
+        let mut lines = "No more moves".to_string();
+        if counts {
+            lines += "\n(Leaving now counts as a loss)";
+        }
+        lines += r#"
+u: Undo last move
+r: Restart this deal
+n: New deal
+Esc: Keep playing  q: Quit"#;
+        self.draw_text_box(&lines);
+
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
+    }
+
+    /// Replace the frame with a notice that the screen is too small for the board
+    pub(super) fn display_too_small(&mut self) {
+        let (width, height) = self.layout.size();
+        let (min_width, min_height) = Layout::MIN_SIZE;
+        self.backend
+            .set_colors(self.default_fg(), self.default_bg());
+        self.backend.clear();
+        self.backend.draw_text(1, 1, "Terminal too small");
+        self.backend.draw_text(
+            1,
+            2,
+            &format!("Need {min_width}x{min_height}, have {width}x{height}"),
+        );
+    }
+
+    fn display_title(&mut self) {
+        self.set_colors(self.theme.palette().title, self.default_bg());
+        let suits = self.symbol("♥ ♠ ♦ ♣", "H S D C");
+        self.draw_text(16, 1, format!("Solitext    {suits}").as_str());
+    }
+
+    /// `saved_game_line`: a line about the saved game, if any
+    pub fn display_start_screen(&mut self, next_seed: Option<u64>, saved_game_line: Option<&str>) {
+        self.clear_screen();
+        self.display_title();
+
+        let seed = next_seed.map_or("random".to_string(), |seed| seed.to_string());
+        let mut lines = format!(
+            r#"1: New Game (Draw One)
 3: New Game (Draw Three)
-Esc: Quit"#;
-        self.draw_text_box(lines);
+s: Seed: {seed}
+t: Statistics  o: Options
+Esc: Quit"#
+        );
+        if let Some(line) = saved_game_line {
+            lines = format!("{line}\n{lines}");
+        }
+        self.draw_text_box(&lines);
 
-        self.set_colors(Self::default_fg(), Self::default_bg());
-        self.stdout.flush().unwrap();
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
     }
 
-    pub fn display_game_menu(&mut self, game_state: &mut GameState) {
+    pub fn display_seed_entry(&mut self, text: &str) {
+        self.clear_screen();
+        self.display_title();
+
+        let lines = format!(
+            r#"Seed for the next new game:
+{text}_
+
+Enter: OK  Esc: Cancel"#
+        );
+        self.draw_text_box(&lines);
+
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
+    }
+
+    pub fn display_stats(&mut self, text: &str) {
+        self.clear_screen();
+        self.display_title();
+        self.draw_text_box(text);
+
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
+    }
+
+    /// `rules`: the rules for new games (apart from the draw count);
+    /// `auto_play`: whether safe cards go to the foundations automatically;
+    /// `bankroll`: the cumulative Vegas bankroll, if it's on
+    pub fn display_options(
+        &mut self,
+        scoring: Scoring,
+        rules: Rules,
+        auto_play: bool,
+        bankroll: Option<i32>,
+    ) {
+        self.clear_screen();
+        self.display_title();
+
+        let passes = match (scoring, rules.pass_limit) {
+            (Scoring::Vegas, _) => "Vegas rules".to_string(),
+            (_, None) => "Unlimited".to_string(),
+            (_, Some(pass_limit)) => pass_limit.to_string(),
+        };
+
+        let bankroll = match bankroll {
+            Some(bankroll) => format!("{} (r: Reset)", Scoring::Vegas.format(bankroll)),
+            None => "Off".to_string(),
+        };
+        let lines = format!(
+            r#"s: Scoring: {}
+p: Stock passes: {passes}
+e: Empty column: {}
+f: Foundation to column: {}
+a: Auto flip: {}
+m: Auto-move safe cards: {}
+b: Vegas bankroll: {bankroll}
+Esc: Back (rules apply next game)"#,
+            scoring.name(),
+            rules.empty_column.name(),
+            if rules.foundation_to_tableau {
+                "Yes"
+            } else {
+                "No"
+            },
+            if rules.auto_flip { "On" } else { "Off" },
+            if auto_play { "On" } else { "Off" },
+        );
+        self.draw_text_box(&lines);
+
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
+    }
+
+    pub fn display_game_menu(&mut self, game_state: &GameState) {
         self.clear_screen();
         //just display cards
         self.display_deck(game_state);
         self.display_columns(game_state);
         self.display_piles(game_state);
 
-        let lines = r#"1: New Game (Draw One)
-3: New Game (Draw Three)
+        let lines = r#"1/3: New Game (Draw One/Three)
 r: Restart current game
-q: Quit
+w: Is this game winnable?
+t: Statistics  o: Options
+s: Save game  q: Save and quit
 Esc: Return to game"#;
         self.draw_text_box(lines);
 
-        self.set_colors(Self::default_fg(), Self::default_bg());
-        self.stdout.flush().unwrap();
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
     }
 
-    pub fn display_help(&mut self, game_state: &mut GameState) {
+    pub fn display_help(&mut self, game_state: &GameState) {
         self.clear_screen();
         //just display cards
         self.display_deck(game_state);
@@ -112,15 +278,69 @@ Esc: Return to game"#;
         self.display_piles(game_state);
 
         let lines = r#"Controls:
-
  Arrow keys, Home, End: Move cursor
- Enter: Hit/move card to stack
+ Enter: Hit/move to stack/turn up
  Space: Select/move cards
  x: Clear selection
+ u: Undo  r: Redo  Tab: Hint
  Ctrl+c: Quit"#;
         self.draw_text_box(lines);
 
-        self.set_colors(Self::default_fg(), Self::default_bg());
-        self.stdout.flush().unwrap();
+        self.set_colors(self.default_fg(), self.default_bg());
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::draw::{Grid, Layout, Theme};
+
+    /// A grid just big enough for the board
+    fn board_grid() -> Grid {
+        let (width, height) = Layout::MIN_SIZE;
+        Grid::new(width, height)
+    }
+
+    fn grid_draw() -> (Draw<Grid>, GameState) {
+        let draw = Draw::with_backend(board_grid(), Theme::Classic, true);
+        (draw, GameState::init(Card::ordered_deck()))
+    }
+
+    #[test]
+    fn test_display_help() {
+        let (mut draw, game_state) = grid_draw();
+        draw.display_help(&game_state);
+        let screen = r#"                #######################################
+ st    st   st  #Controls:#############################
+            st  # Arrow keys, Home, End: Move cursor###
+                # Enter: Hit/move to stack/turn up#####
+                # Space: Select/move cards#############
+                # x: Clear selection###################
+                # u: Undo  r: Redo  Tab: Hint##########
+                # Ctrl+c: Quit#########################
+                #######################################"#;
+        assert_eq!(draw.backend().text(), screen);
+    }
+
+    #[test]
+    fn test_display_game_menu() {
+        let (mut draw, game_state) = grid_draw();
+        draw.display_game_menu(&game_state);
+        let screen = r#"                #######################################
+ st    st   st  #1/3: New Game (Draw One/Three)########
+            st  #r: Restart current game###############
+                #w: Is this game winnable?#############
+                #t: Statistics  o: Options#############
+                #s: Save game  q: Save and quit########
+                #Esc: Return to game###################
+                #######################################
+                #######################################"#;
+        assert_eq!(draw.backend().text(), screen);
+
+        let palette = Theme::Classic.palette();
+        let text = draw.backend().cell(18, 2).unwrap();
+        assert_eq!((text.foreground, text.background), palette.text_box);
     }
 }
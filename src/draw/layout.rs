@@ -0,0 +1,97 @@
+//! Screen positions, worked out from the terminal size.
+//!
+//! Everything is drawn in board positions (as if the board were at the top left),
+//! which the layout moves to center the board on the screen.
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Layout {
+    /// The screen size, as (columns, rows)
+    size: (usize, usize),
+    /// How many cards of each tableau column to show (longer columns scroll)
+    column_cards: usize,
+    /// Where the board's top left corner is on the screen
+    origin: (usize, usize),
+}
+
+impl Layout {
+    pub(super) const BOARD_WIDTH: usize = 72;
+    /// Board rows apart from the columns' cards:
+    /// the title, a gap, the cursor, and the help and message lines
+    const OTHER_ROWS: usize = 6;
+    const MIN_COLUMN_CARDS: usize = 7;
+    /// The longest a column can get: 6 face-down cards, then King to Ace
+    const MAX_COLUMN_CARDS: usize = 19;
+
+    /// The smallest screen that the board fits on, as (columns, rows)
+    pub const MIN_SIZE: (usize, usize) =
+        (Self::BOARD_WIDTH, Self::MIN_COLUMN_CARDS + Self::OTHER_ROWS);
+
+    /// The board's middle column, which boxes and messages are centered on
+    pub(super) const CENTER_COL: usize = Self::BOARD_WIDTH / 2;
+
+    pub fn new(size: (usize, usize)) -> Self {
+        let (width, height) = size;
+        // (Columns get any spare rows)
+        let column_cards = height
+            .saturating_sub(Self::OTHER_ROWS)
+            .clamp(Self::MIN_COLUMN_CARDS, Self::MAX_COLUMN_CARDS);
+        let board_height = column_cards + Self::OTHER_ROWS;
+        Self {
+            size,
+            column_cards,
+            origin: (
+                width.saturating_sub(Self::BOARD_WIDTH) / 2 + 1,
+                height.saturating_sub(board_height) / 2 + 1,
+            ),
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn column_cards(&self) -> usize {
+        self.column_cards
+    }
+
+    /// Whether the whole board fits on the screen
+    pub fn fits(&self) -> bool {
+        let (width, height) = self.size;
+        let (min_width, min_height) = Self::MIN_SIZE;
+        width >= min_width && height >= min_height
+    }
+
+    /// The screen position of a (1-based) board position
+    pub fn position(&self, col: usize, row: usize) -> (usize, usize) {
+        (self.origin.0 + col - 1, self.origin.1 + row - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let a = Layout::new(Layout::MIN_SIZE);
+        assert!(a.fits());
+        assert_eq!(a.column_cards(), 7);
+        assert_eq!(a.position(1, 1), (1, 1));
+
+        // Taller columns on a taller screen
+        let a = Layout::new((80, 24));
+        assert!(a.fits());
+        assert_eq!(a.column_cards(), 18);
+        assert_eq!(a.position(1, 1), (5, 1));
+        assert_eq!(a.position(72, 24), (76, 24));
+
+        // ...up to the longest possible column, with the board centered
+        let a = Layout::new((72, 35));
+        assert_eq!(a.column_cards(), 19);
+        assert_eq!(a.position(1, 1), (1, 6));
+
+        let a = Layout::new((80, 10));
+        assert!(!a.fits());
+        assert_eq!(a.column_cards(), 7);
+    }
+}
@@ -0,0 +1,112 @@
+//! Color themes.
+
+use super::color::Color::{self, *};
+use std::str::FromStr;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Dark,
+    /// Black & white only: red cards are told apart by suit
+    Mono,
+}
+
+impl Theme {
+    pub const NAMES: &'static str = "classic, dark, mono";
+
+    pub fn palette(self) -> &'static Palette {
+        match self {
+            Self::Classic => &CLASSIC,
+            Self::Dark => &DARK,
+            Self::Mono => &MONO,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => Ok(Self::Classic),
+            "dark" => Ok(Self::Dark),
+            "mono" => Ok(Self::Mono),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Colors for each part of the display, as (foreground, background) where there's a pair
+#[derive(Debug)]
+pub struct Palette {
+    pub foreground: Color,
+    pub background: Color,
+    pub title: Color,
+    pub label: Color,
+    pub red_card: (Color, Color),
+    pub black_card: (Color, Color),
+    pub card_back: (Color, Color),
+    pub empty_stock: (Color, Color),
+    pub empty_pile: (Color, Color),
+    pub scroll_arrows: (Color, Color),
+    pub collection_cursor: Color,
+    pub card_cursor: (Color, Color),
+    pub selected: (Color, Color),
+    pub text_box_border: Color,
+    pub text_box: (Color, Color),
+}
+
+const CLASSIC: Palette = Palette {
+    foreground: LightWhite,
+    background: Black,
+    title: LightYellow,
+    label: LightBlack,
+    red_card: (Red, White),
+    black_card: (Black, White),
+    card_back: (LightGreen, LightBlack),
+    empty_stock: (Green, LightBlack),
+    empty_pile: (Blue, LightBlack),
+    scroll_arrows: (White, Green),
+    collection_cursor: Blue,
+    card_cursor: (LightWhite, LightGreen),
+    selected: (LightWhite, LightYellow),
+    text_box_border: LightBlue,
+    text_box: (LightBlack, White),
+};
+
+const DARK: Palette = Palette {
+    foreground: LightWhite,
+    background: Black,
+    title: LightYellow,
+    label: LightBlack,
+    red_card: (LightRed, LightBlack),
+    black_card: (LightWhite, LightBlack),
+    card_back: (Green, Black),
+    empty_stock: (Green, Black),
+    empty_pile: (LightBlue, Black),
+    scroll_arrows: (LightGreen, Black),
+    collection_cursor: LightBlue,
+    card_cursor: (LightWhite, Green),
+    selected: (Black, LightYellow),
+    text_box_border: Blue,
+    text_box: (LightWhite, LightBlack),
+};
+
+const MONO: Palette = Palette {
+    foreground: LightWhite,
+    background: Black,
+    title: LightWhite,
+    label: White,
+    red_card: (Black, White),
+    black_card: (Black, White),
+    card_back: (White, LightBlack),
+    empty_stock: (White, LightBlack),
+    empty_pile: (White, LightBlack),
+    scroll_arrows: (Black, White),
+    collection_cursor: White,
+    card_cursor: (Black, White),
+    selected: (LightWhite, LightBlack),
+    text_box_border: White,
+    text_box: (Black, White),
+};
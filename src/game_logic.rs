@@ -1,6 +1,19 @@
+mod hints;
+mod move_error;
+mod moves;
+mod score;
+
+pub use hints::hints;
+pub use move_error::MoveError;
+pub use moves::Move;
+pub use score::{penalties, score, time_bonus, Scoring};
+
 use crate::cards::{Card, Rank};
-use crate::game_state::{CardState, GameState};
+use crate::game_state::{CardCollection, CardState, EmptyColumn, GameState};
 use crate::selection::Selection;
+use crate::solver;
+use std::cmp::min;
+use std::collections::HashSet;
 
 pub fn victory(game_state: &GameState) -> bool {
     for pile in &game_state.card_piles {
@@ -15,80 +28,100 @@ pub fn victory(game_state: &GameState) -> bool {
     true
 }
 
-fn valid_move_deck_to_pile(pile_index: usize, game_state: &mut GameState) -> Result<(), ()> {
-    use Selection::{Deck, Pile};
-    let deck_card = Deck.selected_collection(game_state).peek().ok_or(())?;
-    let pile_card = Pile { index: pile_index }
-        .selected_collection(game_state)
-        .peek();
-
-    if deck_card.suit as usize != pile_index {
-        //wrong pile
-        return Err(());
+fn valid_move_card_to_pile(
+    card: Card,
+    pile_index: usize,
+    game_state: &GameState,
+) -> Result<(), MoveError> {
+    if card.suit as usize != pile_index {
+        return Err(MoveError::WrongSuit);
     }
 
-    if let Some(pile_card) = pile_card {
-        if deck_card.rank as usize == pile_card.rank as usize + 1 {
-            Ok(())
-        } else {
-            Err(())
-        }
-    } else if deck_card.rank == Rank::Ace {
+    let pile_card = Selection::Pile { index: pile_index }
+        .collection(game_state)?
+        .peek();
+    let next_rank = pile_card.map_or(Rank::Ace as usize, |pile_card| pile_card.rank as usize + 1);
+    if card.rank as usize == next_rank {
         Ok(())
     } else {
-        Err(())
+        Err(MoveError::RankNotNext)
     }
 }
 
+fn valid_move_deck_to_pile(pile_index: usize, game_state: &GameState) -> Result<(), MoveError> {
+    let deck_card = Selection::Deck
+        .collection(game_state)?
+        .peek()
+        .ok_or(MoveError::EmptySource)?;
+    valid_move_card_to_pile(deck_card, pile_index, game_state)
+}
+
 fn valid_move_card_to_column(
     card: Card,
     column_index: usize,
-    game_state: &mut GameState,
-) -> Result<(), ()> {
+    game_state: &GameState,
+) -> Result<(), MoveError> {
     use Selection::Column;
     let column_card = Column {
         index: column_index,
         card_count: 0,
     }
-    .selected_collection(game_state)
+    .collection(game_state)?
     .peek();
 
     if let Some(column_card) = column_card {
-        if card.rank as usize + 1 == column_card.rank as usize
-            && card.suit.is_red() != column_card.suit.is_red()
-        {
-            Ok(())
+        if card.rank as usize + 1 != column_card.rank as usize {
+            Err(MoveError::RankNotOneLower)
+        } else if card.suit.is_red() == column_card.suit.is_red() {
+            Err(MoveError::SameColor)
         } else {
-            Err(())
+            Ok(())
         }
-    } else if card.rank == Rank::King {
+    } else if card.rank == Rank::King || game_state.rules.empty_column == EmptyColumn::AnyCard {
         Ok(())
     } else {
-        Err(())
+        Err(MoveError::KingsOnly)
     }
 }
 
-fn valid_move_deck_to_column(column_index: usize, game_state: &mut GameState) -> Result<(), ()> {
+fn valid_move_deck_to_column(column_index: usize, game_state: &GameState) -> Result<(), MoveError> {
     use Selection::Deck;
-    let deck_card = Deck.selected_collection(game_state).peek().ok_or(())?;
+    let deck_card = Deck
+        .collection(game_state)?
+        .peek()
+        .ok_or(MoveError::EmptySource)?;
     valid_move_card_to_column(deck_card, column_index, game_state)
 }
 
+/// Check that the last `card_count` cards of a column can be picked up
+/// Returns: the first (lowest) of those cards
+fn column_cards(
+    column_index: usize,
+    card_count: usize,
+    game_state: &GameState,
+) -> Result<Card, MoveError> {
+    let column = game_state
+        .columns
+        .get(column_index)
+        .ok_or(MoveError::NoSuchColumn)?;
+    let cards = column.peek_n(card_count).ok_or(MoveError::EmptySource)?;
+    let first_card = cards.first().copied().ok_or(MoveError::EmptySource)?;
+    if card_count > column.face_up_cards() {
+        return Err(MoveError::FaceDown);
+    }
+    Ok(first_card)
+}
+
 fn valid_move_column_to_column(
     from_index: usize,
     card_count: usize,
     to_index: usize,
-    game_state: &mut GameState,
-) -> Result<(), ()> {
-    let cards = Selection::Column {
-        index: from_index,
-        card_count,
+    game_state: &GameState,
+) -> Result<(), MoveError> {
+    if from_index == to_index {
+        return Err(MoveError::NoSuchMove);
     }
-    .selected_collection(game_state)
-    .peek_n(card_count)
-    .ok_or(())?;
-
-    let first_card = cards.first().copied().ok_or(())?;
+    let first_card = column_cards(from_index, card_count, game_state)?;
     valid_move_card_to_column(first_card, to_index, game_state)
 }
 
@@ -96,71 +129,53 @@ fn valid_move_column_to_pile(
     column_index: usize,
     card_count: usize,
     pile_index: usize,
-    game_state: &mut GameState,
-) -> Result<(), ()> {
-    use Selection::{Column, Pile};
-
+    game_state: &GameState,
+) -> Result<(), MoveError> {
     if card_count != 1 {
-        return Err(());
+        return Err(MoveError::MultipleCards);
     }
-
-    let column_card = Column {
-        index: column_index,
-        card_count,
-    }
-    .selected_collection(game_state)
-    .peek()
-    .ok_or(())?;
-    if column_card.suit as usize != pile_index {
-        return Err(());
-    }
-
-    let pile_card = Pile { index: pile_index }
-        .selected_collection(game_state)
-        .peek();
-
-    if let Some(pile_card) = pile_card {
-        if column_card.rank as usize == pile_card.rank as usize + 1 {
-            return Ok(());
-        }
-    } else if column_card.rank == Rank::Ace {
-        return Ok(());
-    }
-
-    Err(())
+    let column_card = column_cards(column_index, card_count, game_state)?;
+    valid_move_card_to_pile(column_card, pile_index, game_state)
 }
 
 fn valid_move_pile_to_column(
     pile_index: usize,
     column_index: usize,
-    game_state: &mut GameState,
-) -> Result<(), ()> {
+    game_state: &GameState,
+) -> Result<(), MoveError> {
+    if !game_state.rules.foundation_to_tableau {
+        return Err(MoveError::FoundationLocked);
+    }
     let card = Selection::Pile { index: pile_index }
-        .selected_collection(game_state)
+        .collection(game_state)?
         .peek()
-        .ok_or(())?;
+        .ok_or(MoveError::EmptySource)?;
 
     valid_move_card_to_column(card, column_index, game_state)
 }
 
-pub fn valid_move(from: Selection, to: Selection, game_state: &mut GameState) -> Result<(), ()> {
+pub(crate) fn valid_move(
+    from: Selection,
+    to: Selection,
+    game_state: &GameState,
+) -> Result<(), MoveError> {
     use Selection::{Column, Deck, Pile};
     match from {
         Deck => match to {
-            Deck => Err(()),
+            Deck => Err(MoveError::NoSuchMove),
             Pile { index } => valid_move_deck_to_pile(index, game_state),
             Column { index, .. } => valid_move_deck_to_column(index, game_state),
         },
         Pile { index } => match to {
-            Deck => Err(()),
-            Pile { .. } => Err(()),
+            Deck => Err(MoveError::NoSuchMove),
+            Pile { .. } => Err(MoveError::NoSuchMove),
             Column {
                 index: column_index,
                 ..
             } => valid_move_pile_to_column(index, column_index, game_state),
         },
         Column { index, card_count } => match to {
-            Deck => Err(()),
+            Deck => Err(MoveError::NoSuchMove),
             Pile { index: pile_index } => {
                 valid_move_column_to_pile(index, card_count, pile_index, game_state)
             }
@@ -171,13 +186,272 @@ pub fn valid_move(from: Selection, to: Selection, game_state: &mut GameState) ->
     }
 }
 
+/// Check a move against the game rules
+pub fn valid(mv: Move, game_state: &GameState) -> Result<(), MoveError> {
+    match mv {
+        Move::Draw { card_count } => {
+            if game_state.deck.is_empty() {
+                Err(MoveError::EmptySource)
+            } else if card_count != min(game_state.draw_count(), game_state.deck.len()) {
+                Err(MoveError::WrongDrawCount)
+            } else {
+                Ok(())
+            }
+        }
+        Move::Recycle => {
+            if !game_state.deck.is_empty() {
+                Err(MoveError::StockNotEmpty)
+            } else if game_state.deck_drawn.is_empty() {
+                Err(MoveError::EmptySource)
+            } else if !game_state.can_recycle() {
+                Err(MoveError::NoMorePasses)
+            } else {
+                Ok(())
+            }
+        }
+        Move::Flip { column } => match game_state
+            .columns
+            .get(column)
+            .ok_or(MoveError::NoSuchMove)?
+            .0
+            .last()
+        {
+            Some((_, CardState::FaceDown)) => Ok(()),
+            _ => Err(MoveError::NotFaceDown),
+        },
+        _ => {
+            let (from, to) = mv.selections().ok_or(MoveError::NoSuchMove)?;
+            valid_move(from, to, game_state)
+        }
+    }
+}
+
+/// Check a move against the game rules, then perform it
+pub fn play(mv: Move, game_state: &mut GameState) -> Result<(), MoveError> {
+    valid(mv, game_state)?;
+    mv.apply(game_state)
+}
+
+/// Every move allowed by the game rules in `game_state`
+pub fn legal_moves(game_state: &GameState) -> Vec<Move> {
+    let columns = 0..GameState::COLUMN_COUNT;
+    let piles = 0..GameState::CARD_PILES_COUNT;
+    let mut moves = vec![];
+
+    for from in columns.clone() {
+        for card_count in 1..=game_state.columns[from].face_up_cards() {
+            moves.extend(columns.clone().map(|to| Move::ColumnToColumn {
+                from,
+                to,
+                card_count,
+            }));
+        }
+        moves.extend(
+            piles
+                .clone()
+                .map(|pile| Move::ColumnToPile { column: from, pile }),
+        );
+        moves.push(Move::Flip { column: from });
+    }
+    for pile in piles.clone() {
+        moves.push(Move::DeckToPile { pile });
+        moves.extend(
+            columns
+                .clone()
+                .map(|column| Move::PileToColumn { pile, column }),
+        );
+    }
+    moves.extend(columns.map(|column| Move::DeckToColumn { column }));
+    moves.push(Move::Draw {
+        card_count: min(game_state.draw_count(), game_state.deck.len()),
+    });
+    moves.push(Move::Recycle);
+
+    moves.retain(|&mv| valid(mv, game_state).is_ok());
+    moves
+}
+
+/// Is this a move to a foundation that can't block any other card?
+/// (That is: an Ace or a 2, or both cards of the opposite color that could go on it are already home.)
+pub fn safe_to_foundation(mv: Move, game_state: &GameState) -> bool {
+    let card = match mv {
+        Move::ColumnToPile { column, .. } => game_state.columns.get(column).and_then(|c| c.peek()),
+        Move::DeckToPile { .. } => game_state.deck_drawn.peek(),
+        _ => None,
+    };
+    let Some(card) = card else {
+        return false;
+    };
+    if card.rank <= Rank::R2 {
+        return true;
+    }
+
+    game_state
+        .card_piles
+        .iter()
+        .filter_map(CardCollection::peek)
+        .filter(|pile_card| pile_card.suit.is_red() != card.suit.is_red())
+        .filter(|pile_card| pile_card.rank as usize + 1 >= card.rank as usize)
+        .count()
+        == 2
+}
+
+/// A legal move to a foundation that can't block any other card, if there is one
+pub fn safe_foundation_move(game_state: &GameState) -> Option<Move> {
+    legal_moves(game_state)
+        .into_iter()
+        .find(|&mv| safe_to_foundation(mv, game_state))
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StalemateResult {
+    /// No sequence of moves gets anywhere
+    Stuck,
+    /// Some sequence of moves gets a card home, or turns one up
+    NotStuck,
+    /// There were too many positions to search through
+    Unknown,
+}
+
+/// Is the game stuck? That is: no sequence of legal moves (including going through the stock,
+/// splitting runs, and moving cards back from the foundations) gets a card to a foundation
+/// that isn't there now, or turns up a face-down card.
+pub fn stalemate(game_state: &GameState) -> StalemateResult {
+    const MAX_STATES: usize = 2_000;
+
+    // (A won game isn't stuck, though nothing gets anywhere from it)
+    if victory(game_state) {
+        return StalemateResult::NotStuck;
+    }
+
+    let home: HashSet<Card> = game_state
+        .card_piles
+        .iter()
+        .flat_map(|pile| pile.0.iter().copied())
+        .collect();
+    let face_down_cards = |game_state: &GameState| {
+        game_state
+            .columns
+            .iter()
+            .flat_map(|column| &column.0)
+            .filter(|(_, card_state)| *card_state == CardState::FaceDown)
+            .count()
+    };
+    let face_down = face_down_cards(game_state);
+    let progress = |game_state: &GameState| {
+        face_down_cards(game_state) < face_down
+            || game_state
+                .card_piles
+                .iter()
+                .flat_map(|pile| &pile.0)
+                .any(|card| !home.contains(card))
+    };
+
+    // Depth-first search of every position reachable from here
+    let mut visited = HashSet::from([solver::state_hash(game_state)]);
+    let mut positions = vec![game_state.clone()];
+    while let Some(mut position) = positions.pop() {
+        for mv in legal_moves(&position) {
+            mv.apply(&mut position).expect("legal move should apply");
+            if progress(&position) {
+                return StalemateResult::NotStuck;
+            }
+            if visited.insert(solver::state_hash(&position)) {
+                if visited.len() > MAX_STATES {
+                    return StalemateResult::Unknown;
+                }
+                positions.push(position.clone());
+            }
+            mv.revert(&mut position)
+                .expect("applied move should revert");
+        }
+    }
+    StalemateResult::Stuck
+}
+
+/// Can the game be finished just by moving column cards to the foundations?
+/// (True once the stock and drawn pile are empty, and all column cards are face-up)
+pub fn can_auto_complete(game_state: &GameState) -> bool {
+    game_state.deck.is_empty()
+        && game_state.deck_drawn.is_empty()
+        && game_state
+            .columns
+            .iter()
+            .all(|column| column.face_up_cards() == column.0.len())
+        && !victory(game_state)
+}
+
+/// The next move to finish the game automatically: the lowest card that can go to a foundation
+pub fn auto_complete_move(game_state: &GameState) -> Option<Move> {
+    let mut moves = vec![];
+    for (index, column) in game_state.columns.iter().enumerate() {
+        let Some(&(card, _)) = column.0.last() else {
+            continue;
+        };
+        let from = Selection::Column {
+            index,
+            card_count: 1,
+        };
+        for pile in 0..GameState::CARD_PILES_COUNT {
+            if valid_move(from, Selection::Pile { index: pile }, game_state).is_ok() {
+                moves.push((
+                    card.rank,
+                    Move::ColumnToPile {
+                        column: index,
+                        pile,
+                    },
+                ));
+            }
+        }
+    }
+    moves
+        .into_iter()
+        .min_by_key(|&(rank, _)| rank)
+        .map(|(_, mv)| mv)
+}
+
+/// Hit the stock, turning the drawn pile over first if the stock is empty
+/// (and that's allowed)
+/// Returns: the moves applied
+pub fn deck_hit(game_state: &mut GameState) -> Vec<Move> {
+    let mut moves = vec![];
+    if valid(Move::Recycle, game_state).is_ok() {
+        moves.push(Move::Recycle);
+        Move::Recycle
+            .apply(game_state)
+            .expect("valid recycle should apply");
+    }
+
+    let card_count = min(game_state.draw_count(), game_state.deck.len());
+    if card_count > 0 {
+        let mv = Move::Draw { card_count };
+        mv.apply(game_state).expect("valid draw should apply");
+        moves.push(mv);
+    }
+    moves
+}
+
+/// Turn up uncovered face-down column cards, if the rules do that automatically
+/// Returns: the moves applied
+pub fn auto_flip(game_state: &mut GameState) -> Vec<Move> {
+    if game_state.rules.auto_flip {
+        face_up_on_columns(game_state)
+    } else {
+        vec![]
+    }
+}
+
 /// Ensure all card columns end with at least one face-up card
-pub fn face_up_on_columns(game_state: &mut GameState) {
-    for column in &mut game_state.columns {
-        if let Some((_, card_state)) = column.0.last_mut() {
+/// Returns: the moves applied
+pub fn face_up_on_columns(game_state: &mut GameState) -> Vec<Move> {
+    let mut moves = vec![];
+    for (column, cards) in game_state.columns.iter_mut().enumerate() {
+        if let Some((_, card_state @ CardState::FaceDown)) = cards.0.last_mut() {
             *card_state = CardState::FaceUp;
+            moves.push(Move::Flip { column });
         }
     }
+    moves
 }
 
 #[cfg(test)]
@@ -190,4 +464,250 @@ mod test {
         assert!(!victory(&GameState::almost_victory()));
         assert!(!victory(&GameState::init(Card::ordered_deck())));
     }
+
+    #[test]
+    fn test_legal_moves() {
+        let mut a = GameState::init(Card::ordered_deck());
+        assert_eq!(
+            legal_moves(&a),
+            (0..GameState::COLUMN_COUNT)
+                .map(|column| Move::Flip { column })
+                .chain([Move::Draw { card_count: 1 }])
+                .collect::<Vec<_>>()
+        );
+
+        face_up_on_columns(&mut a);
+        let moves = legal_moves(&a);
+        assert_eq!(
+            moves,
+            vec![
+                // J♣ onto Q♦
+                Move::ColumnToColumn {
+                    from: 1,
+                    to: 4,
+                    card_count: 1
+                },
+                // Q♦ onto K♣
+                Move::ColumnToColumn {
+                    from: 4,
+                    to: 0,
+                    card_count: 1
+                },
+                Move::Draw { card_count: 1 },
+            ]
+        );
+        for mv in moves {
+            mv.apply(&mut a.clone()).unwrap();
+        }
+
+        // Only the Kings can move back to the (empty) columns
+        let moves = legal_moves(&GameState::victory());
+        assert_eq!(
+            GameState::CARD_PILES_COUNT * GameState::COLUMN_COUNT,
+            moves.len()
+        );
+        assert!(moves
+            .iter()
+            .all(|mv| matches!(mv, Move::PileToColumn { .. })));
+    }
+
+    #[test]
+    fn test_legal_moves_stock() {
+        let mut a = GameState::init(Card::ordered_deck());
+        face_up_on_columns(&mut a);
+        while !a.deck.is_empty() {
+            assert!(legal_moves(&a).contains(&Move::Draw { card_count: 1 }));
+            deck_hit(&mut a);
+        }
+        let moves = legal_moves(&a);
+        assert!(moves.contains(&Move::Recycle));
+        assert!(!moves.iter().any(|mv| matches!(mv, Move::Draw { .. })));
+    }
+
+    #[test]
+    fn test_deck_hit() {
+        let mut a = GameState::init(Card::ordered_deck());
+        a.rules.draw_count = 3;
+
+        for _ in 0..8 {
+            assert_eq!(vec![Move::Draw { card_count: 3 }], deck_hit(&mut a));
+        }
+        assert!(a.deck.is_empty());
+        assert_eq!(
+            vec![Move::Recycle, Move::Draw { card_count: 3 }],
+            deck_hit(&mut a)
+        );
+        assert_eq!(21, a.deck.len());
+    }
+
+    #[test]
+    fn test_pass_limit() {
+        let mut a = GameState::init(Card::ordered_deck());
+        a.rules.draw_count = 3;
+        a.rules.pass_limit = Some(2);
+
+        for _ in 0..8 {
+            deck_hit(&mut a);
+        }
+        assert!(valid(Move::Recycle, &a).is_ok());
+        for _ in 0..8 {
+            deck_hit(&mut a);
+        }
+        assert_eq!(1, a.recycle_count);
+        assert!(valid(Move::Recycle, &a).is_err());
+        assert!(deck_hit(&mut a).is_empty());
+        assert!(!legal_moves(&a).contains(&Move::Recycle));
+    }
+
+    #[test]
+    fn test_auto_complete() {
+        let mut a = GameState::almost_victory();
+        // (K♥ on the last column, and Q♥ under it)
+        let queen = a.card_piles[0].0.pop().unwrap();
+        a.columns[6].0.push((queen, CardState::FaceUp));
+        assert!(can_auto_complete(&a));
+        assert!(!can_auto_complete(&GameState::victory()));
+        assert!(!can_auto_complete(&GameState::init(Card::ordered_deck())));
+
+        let mut moves = 0;
+        while let Some(mv) = auto_complete_move(&a) {
+            mv.apply(&mut a).unwrap();
+            moves += 1;
+        }
+        assert_eq!(moves, 2);
+        assert!(victory(&a));
+    }
+
+    #[test]
+    fn test_safe_foundation_move() {
+        let mut a: GameState = "c1: 3H\nc2: AS\nf1: AH 2H\nf4: AC 2C".parse().unwrap();
+        assert_eq!(
+            safe_foundation_move(&a),
+            Some(Move::ColumnToPile { column: 1, pile: 1 })
+        );
+        // 3♥ isn't safe until 2♠ is home too
+        assert!(!safe_to_foundation(
+            Move::ColumnToPile { column: 0, pile: 0 },
+            &a
+        ));
+        a.card_piles[1]
+            .0
+            .extend(["AS".parse::<Card>().unwrap(), "2S".parse().unwrap()]);
+        a.columns[1].0.clear();
+        assert_eq!(
+            safe_foundation_move(&a),
+            Some(Move::ColumnToPile { column: 0, pile: 0 })
+        );
+    }
+
+    #[test]
+    fn test_stalemate() {
+        use StalemateResult::{NotStuck, Stuck};
+
+        // Twos of Hearts through 8, with no Aces or empty columns: no moves at all
+        let mut a: GameState = "c1: 2H\nc2: 3H\nc3: 4H\nc4: 5H\nc5: 6H\nc6: 7H\nc7: 8H"
+            .parse()
+            .unwrap();
+        assert_eq!(stalemate(&a), Stuck);
+        // A stock of unplayable cards doesn't help
+        a.deck = vec!["9H".parse().unwrap(), "TH".parse().unwrap()];
+        a.deck_drawn = vec!["JH".parse().unwrap()];
+        assert_eq!(stalemate(&a), Stuck);
+        // ...but an Ace in it does
+        a.deck.insert(0, "AH".parse().unwrap());
+        assert_eq!(stalemate(&a), NotStuck);
+        // ...unless it's out of reach, after the last pass
+        a.deck_drawn.splice(0..0, a.deck.drain(..));
+        assert_eq!(stalemate(&a), NotStuck);
+        a.rules.pass_limit = Some(1);
+        assert_eq!(stalemate(&a), Stuck);
+
+        // 5♥ back down onto 6♣, then 4♠ onto it, frees A♠ for a foundation
+        let a: GameState = "draw 3\nwaste: 9D AS 4S\nc1: [KD] 6C\nf1: AH 2H 3H 4H 5H"
+            .parse()
+            .unwrap();
+        assert_eq!(stalemate(&a), NotStuck);
+        // ...but moving cards back and forth like that isn't a way on by itself
+        let a: GameState = "draw 3\nwaste: 9D 4S\nc1: [KD] 6C\nf1: AH 2H 3H 4H 5H"
+            .parse()
+            .unwrap();
+        assert_eq!(stalemate(&a), Stuck);
+        // ...and neither are moves worth suggesting, if they don't lead anywhere
+        let a: GameState = "waste: 7C\nc1: 8H\nc2: 2H\nc3: 3H\nc4: 4H\nc5: 5H\nc6: 6H\nc7: 9H"
+            .parse()
+            .unwrap();
+        assert!(!hints(&a).is_empty());
+        assert_eq!(stalemate(&a), Stuck);
+
+        let mut a = GameState::init(Card::ordered_deck());
+        face_up_on_columns(&mut a);
+        assert_eq!(stalemate(&a), NotStuck);
+        assert_eq!(stalemate(&GameState::victory()), NotStuck);
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut a = GameState::almost_victory();
+        a.columns[0].0.clear();
+        let queen_to_empty = Move::PileToColumn { pile: 0, column: 0 };
+        assert_eq!(valid(queen_to_empty, &a), Err(MoveError::KingsOnly));
+        a.rules.empty_column = EmptyColumn::AnyCard;
+        assert!(valid(queen_to_empty, &a).is_ok());
+        a.rules.foundation_to_tableau = false;
+        assert_eq!(valid(queen_to_empty, &a), Err(MoveError::FoundationLocked));
+
+        let mut a = GameState::init(Card::ordered_deck());
+        a.rules.auto_flip = false;
+        assert!(auto_flip(&mut a).is_empty());
+        a.rules.auto_flip = true;
+        assert_eq!(GameState::COLUMN_COUNT, auto_flip(&mut a).len());
+    }
+
+    #[test]
+    fn test_move_errors() {
+        let a: GameState = "c1: [TS] 9H\nc2: 8D\nc3: 2H\nc4: 8S\nc5: QC\nf1: AH"
+            .parse()
+            .unwrap();
+        let column = |index, card_count| Selection::Column { index, card_count };
+        let pile = |index| Selection::Pile { index };
+        let cases = [
+            (column(2, 1), pile(0), Ok(())),
+            (column(2, 1), pile(1), Err(MoveError::WrongSuit)),
+            (column(1, 1), pile(2), Err(MoveError::RankNotNext)),
+            (column(0, 2), pile(1), Err(MoveError::MultipleCards)),
+            (column(3, 1), column(0, 0), Ok(())),
+            (column(1, 1), column(0, 0), Err(MoveError::SameColor)),
+            (column(2, 1), column(0, 0), Err(MoveError::RankNotOneLower)),
+            (column(4, 1), column(5, 0), Err(MoveError::KingsOnly)),
+            (column(0, 2), column(5, 0), Err(MoveError::FaceDown)),
+            (column(5, 1), column(0, 0), Err(MoveError::EmptySource)),
+            (Selection::Deck, column(0, 0), Err(MoveError::EmptySource)),
+            (pile(0), pile(1), Err(MoveError::NoSuchMove)),
+            (column(7, 1), column(0, 0), Err(MoveError::NoSuchColumn)),
+            (column(3, 1), column(9, 0), Err(MoveError::NoSuchColumn)),
+        ];
+        for (from, to, result) in cases {
+            assert_eq!(valid_move(from, to, &a), result, "{from:?} to {to:?}");
+        }
+
+        let flip = Move::Flip { column: 1 };
+        assert_eq!(valid(flip, &a), Err(MoveError::NotFaceDown));
+        assert_eq!(flip.apply(&mut a.clone()), Err(MoveError::NotFaceDown));
+        assert_eq!(valid(Move::Recycle, &a), Err(MoveError::EmptySource));
+
+        // Out-of-range indexes are errors, even without checking the rules
+        let to_pile = Move::ColumnToPile { column: 2, pile: 4 };
+        assert_eq!(to_pile.apply(&mut a.clone()), Err(MoveError::NoSuchPile));
+        let flip = Move::Flip { column: 7 };
+        assert_eq!(flip.revert(&mut a.clone()), Err(MoveError::NoSuchColumn));
+    }
+
+    #[test]
+    fn test_face_up_on_columns() {
+        let mut a = GameState::init(Card::ordered_deck());
+        let moves = face_up_on_columns(&mut a);
+        assert_eq!(GameState::COLUMN_COUNT, moves.len());
+        assert_eq!(Some(Move::Flip { column: 6 }), moves.last().copied());
+        assert!(face_up_on_columns(&mut a).is_empty());
+    }
 }
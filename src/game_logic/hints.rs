@@ -0,0 +1,129 @@
+//! Ranks legal moves to suggest to the player.
+
+use super::{legal_moves, valid_move, Move};
+use crate::game_state::GameState;
+use crate::selection::Selection;
+
+/// Legal moves worth suggesting, best first
+pub fn hints(game_state: &GameState) -> Vec<Move> {
+    let mut hints: Vec<(i32, Move)> = legal_moves(game_state)
+        .into_iter()
+        .filter_map(|mv| hint_score(mv, game_state).map(|score| (score, mv)))
+        .collect();
+    // (stable sort: ties stay in generated order)
+    hints.sort_by_key(|&(score, _)| -score);
+    hints.into_iter().map(|(_, mv)| mv).collect()
+}
+
+/// Number of face-down cards in a column
+fn face_down_cards(game_state: &GameState, column: usize) -> usize {
+    let cards = &game_state.columns[column];
+    cards.0.len() - cards.face_up_cards()
+}
+
+/// Could the card under the last `card_count` cards of a column go to a foundation?
+fn exposes_foundation_card(game_state: &GameState, column: usize, card_count: usize) -> bool {
+    let mut a = game_state.clone();
+    let cards = &mut a.columns[column].0;
+    cards.truncate(cards.len() - card_count);
+    (0..GameState::CARD_PILES_COUNT).any(|index| {
+        valid_move(
+            Selection::Column {
+                index: column,
+                card_count: 1,
+            },
+            Selection::Pile { index },
+            &a,
+        )
+        .is_ok()
+    })
+}
+
+/// How good a legal move is (higher is better), or None if it isn't worth suggesting
+fn hint_score(mv: Move, game_state: &GameState) -> Option<i32> {
+    const REVEAL: i32 = 40;
+    const FOUNDATION: i32 = 30;
+    const EMPTY_COLUMN: i32 = 20;
+    const FROM_DECK: i32 = 10;
+
+    let score = match mv {
+        Move::Flip { .. } => REVEAL * 2,
+        Move::ColumnToPile { column, .. } => {
+            let hidden = face_down_cards(game_state, column);
+            match game_state.columns[column].0.len() {
+                1 => FOUNDATION + EMPTY_COLUMN,
+                _ if hidden > 0 && game_state.columns[column].face_up_cards() == 1 => {
+                    FOUNDATION + REVEAL + hidden as i32
+                }
+                _ => FOUNDATION,
+            }
+        }
+        Move::ColumnToColumn {
+            from,
+            to,
+            card_count,
+        } => {
+            let hidden = face_down_cards(game_state, from);
+            if card_count < game_state.columns[from].face_up_cards() {
+                // Splitting a run only helps if it lets a card go home
+                if !exposes_foundation_card(game_state, from, card_count) {
+                    return None;
+                }
+                FOUNDATION / 2
+            } else if hidden > 0 {
+                REVEAL + hidden as i32
+            } else if game_state.columns[to].0.is_empty() {
+                // Moving a whole column to another empty column does nothing
+                return None;
+            } else {
+                EMPTY_COLUMN
+            }
+        }
+        Move::DeckToPile { .. } => FOUNDATION,
+        Move::DeckToColumn { .. } => FROM_DECK,
+        Move::PileToColumn { .. } => return None,
+        Move::Draw { .. } | Move::Recycle => 0,
+    };
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::game_logic::face_up_on_columns;
+
+    #[test]
+    fn test_hints() {
+        let mut a = GameState::init(Card::ordered_deck());
+        face_up_on_columns(&mut a);
+
+        // Both column moves reveal a face-down card: Q♦ has more cards under it
+        assert_eq!(
+            hints(&a),
+            vec![
+                Move::ColumnToColumn {
+                    from: 4,
+                    to: 0,
+                    card_count: 1
+                },
+                Move::ColumnToColumn {
+                    from: 1,
+                    to: 4,
+                    card_count: 1
+                },
+                Move::Draw { card_count: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hints_skip_pointless_moves() {
+        let mut a = GameState::almost_victory();
+        // Lone K♥ in column 0: moving it to another empty column is never suggested
+        assert_eq!(hints(&a), vec![Move::ColumnToPile { column: 0, pile: 0 }]);
+
+        a.columns[0].0.clear();
+        assert!(hints(&a).is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+//! Reasons that a move can't be made.
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MoveError {
+    /// A foundation only takes cards of its own suit
+    WrongSuit,
+    /// A column card must go on a card one rank higher
+    RankNotOneLower,
+    /// A foundation builds up from the Ace, one rank at a time
+    RankNotNext,
+    /// Column cards must alternate between red and black
+    SameColor,
+    /// Only a King can go into an empty column (unless the rules allow any card)
+    KingsOnly,
+    /// Foundations take one card at a time
+    MultipleCards,
+    /// There aren't enough cards to move
+    EmptySource,
+    /// Face-down cards can't be moved
+    FaceDown,
+    /// There's no face-down card to turn up
+    NotFaceDown,
+    /// The rules don't allow moving foundation cards back to the columns
+    FoundationLocked,
+    /// The drawn pile can only be turned over once the stock is empty
+    StockNotEmpty,
+    /// The stock has been passed through as many times as the rules allow
+    NoMorePasses,
+    /// Draws must take the number of cards set by the rules (or what's left)
+    WrongDrawCount,
+    /// The move doesn't exist (e.g.: from a collection to itself)
+    NoSuchMove,
+    /// There's no column with that index
+    NoSuchColumn,
+    /// There's no foundation with that index
+    NoSuchPile,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::WrongSuit => "Wrong suit for that foundation",
+            Self::RankNotOneLower => "Cards go on a card one rank higher",
+            Self::RankNotNext => "Foundations build up from the Ace",
+            Self::SameColor => "Cards go on the opposite color",
+            Self::KingsOnly => "Only Kings go in empty columns",
+            Self::MultipleCards => "One card at a time to foundations",
+            Self::EmptySource => "No cards to move",
+            Self::FaceDown => "Face-down cards can't move",
+            Self::NotFaceDown => "No face-down card to turn up",
+            Self::FoundationLocked => "Foundation cards can't move back",
+            Self::StockNotEmpty => "The stock isn't empty yet",
+            Self::NoMorePasses => "No more passes through the stock",
+            Self::WrongDrawCount => "Wrong number of cards drawn",
+            Self::NoSuchMove => "Not a move",
+            Self::NoSuchColumn => "No such column",
+            Self::NoSuchPile => "No such foundation",
+        };
+        write!(f, "{text}")
+    }
+}
@@ -0,0 +1,432 @@
+//! Single game actions, which can be applied to and reverted from a `GameState`.
+
+use super::MoveError;
+use crate::cards::Card;
+use crate::game_state::{CardState, GameState};
+use crate::selection::Selection;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Move {
+    /// Move the last `card_count` cards of one column onto another
+    ColumnToColumn {
+        from: usize,
+        to: usize,
+        card_count: usize,
+    },
+    /// Move the last card of a column to a foundation
+    ColumnToPile { column: usize, pile: usize },
+    /// Move the top card of the drawn pile to a column
+    DeckToColumn { column: usize },
+    /// Move the top card of the drawn pile to a foundation
+    DeckToPile { pile: usize },
+    /// Move the top card of a foundation back to a column
+    PileToColumn { pile: usize, column: usize },
+    /// Draw `card_count` cards from the stock onto the drawn pile
+    Draw { card_count: usize },
+    /// Turn the drawn pile over to make a new stock
+    Recycle,
+    /// Turn the last card of a column face-up
+    Flip { column: usize },
+}
+
+impl Move {
+    /// The move of selected cards from `from` to `to`, if there is one
+    #[cfg(feature = "tui")]
+    pub(crate) fn from_selections(from: Selection, to: Selection) -> Option<Self> {
+        use Selection::{Column, Deck, Pile};
+        match (from, to) {
+            (Deck, Column { index, .. }) => Some(Self::DeckToColumn { column: index }),
+            (Deck, Pile { index }) => Some(Self::DeckToPile { pile: index }),
+            (Pile { index }, Column { index: column, .. }) => Some(Self::PileToColumn {
+                pile: index,
+                column,
+            }),
+            (Column { index, card_count }, Column { index: to, .. })
+                if index != to && card_count > 0 =>
+            {
+                Some(Self::ColumnToColumn {
+                    from: index,
+                    to,
+                    card_count,
+                })
+            }
+            (
+                Column {
+                    index,
+                    card_count: 1,
+                },
+                Pile { index: pile },
+            ) => Some(Self::ColumnToPile {
+                column: index,
+                pile,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The source and destination selections of a card move; None for other moves
+    pub(crate) fn selections(self) -> Option<(Selection, Selection)> {
+        use Selection::{Column, Deck, Pile};
+        let column = |index, card_count| Column { index, card_count };
+        match self {
+            Self::ColumnToColumn {
+                from,
+                to,
+                card_count,
+            } => Some((column(from, card_count), column(to, 0))),
+            Self::ColumnToPile {
+                column: index,
+                pile,
+            } => Some((column(index, 1), Pile { index: pile })),
+            Self::DeckToColumn { column: index } => Some((Deck, column(index, 0))),
+            Self::DeckToPile { pile } => Some((Deck, Pile { index: pile })),
+            Self::PileToColumn {
+                pile,
+                column: index,
+            } => Some((Pile { index: pile }, column(index, 0))),
+            Self::Draw { .. } | Self::Recycle | Self::Flip { .. } => None,
+        }
+    }
+
+    /// Perform this move on `game_state`.
+    /// (This does not check the game rules: see `game_logic::valid_move`)
+    pub fn apply(self, game_state: &mut GameState) -> Result<(), MoveError> {
+        match self {
+            Self::Draw { card_count } => {
+                move_stock_cards(&mut game_state.deck, &mut game_state.deck_drawn, card_count)
+            }
+            Self::Recycle => {
+                turn_over(&mut game_state.deck_drawn, &mut game_state.deck)?;
+                game_state.recycle_count += 1;
+                Ok(())
+            }
+            Self::Flip { column } => {
+                set_last_card_state(game_state, column, CardState::FaceDown, CardState::FaceUp)
+            }
+            _ => {
+                let (from, to) = self.selections().ok_or(MoveError::NoSuchMove)?;
+                transfer(from, to, from.card_count(), game_state)
+            }
+        }
+    }
+
+    /// Undo this move on `game_state`, which must be in the state right after it was applied
+    pub fn revert(self, game_state: &mut GameState) -> Result<(), MoveError> {
+        match self {
+            Self::Draw { card_count } => {
+                move_stock_cards(&mut game_state.deck_drawn, &mut game_state.deck, card_count)
+            }
+            Self::Recycle => {
+                turn_over(&mut game_state.deck, &mut game_state.deck_drawn)?;
+                game_state.recycle_count = game_state
+                    .recycle_count
+                    .checked_sub(1)
+                    .ok_or(MoveError::NoSuchMove)?;
+                Ok(())
+            }
+            Self::Flip { column } => {
+                set_last_card_state(game_state, column, CardState::FaceUp, CardState::FaceDown)
+            }
+            _ => {
+                let (from, to) = self.selections().ok_or(MoveError::NoSuchMove)?;
+                transfer(to, from, from.card_count(), game_state)
+            }
+        }
+    }
+}
+
+/// Text notation, e.g.: "c2>c5x3", "w>f1", "draw:3", "recycle", "flip:c4".
+/// Columns are c1-c7, foundations are f1-f4, and the drawn pile (waste) is w.
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ColumnToColumn {
+                from,
+                to,
+                card_count,
+            } => {
+                write!(f, "c{}>c{}", from + 1, to + 1)?;
+                if *card_count != 1 {
+                    write!(f, "x{card_count}")?;
+                }
+                Ok(())
+            }
+            Self::ColumnToPile { column, pile } => write!(f, "c{}>f{}", column + 1, pile + 1),
+            Self::DeckToColumn { column } => write!(f, "w>c{}", column + 1),
+            Self::DeckToPile { pile } => write!(f, "w>f{}", pile + 1),
+            Self::PileToColumn { pile, column } => write!(f, "f{}>c{}", pile + 1, column + 1),
+            Self::Draw { card_count } => write!(f, "draw:{card_count}"),
+            Self::Recycle => write!(f, "recycle"),
+            Self::Flip { column } => write!(f, "flip:c{}", column + 1),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        /// Parse a 1-based index with a letter prefix, e.g.: "c3" -> 2
+        fn index(s: &str, prefix: char, count: usize) -> Result<usize, ()> {
+            let number: usize = s.strip_prefix(prefix).ok_or(())?.parse().map_err(|_| ())?;
+            number
+                .checked_sub(1)
+                .filter(|&index| index < count)
+                .ok_or(())
+        }
+        let column = |s| index(s, 'c', GameState::COLUMN_COUNT);
+        let pile = |s| index(s, 'f', GameState::CARD_PILES_COUNT);
+
+        if s == "recycle" {
+            return Ok(Self::Recycle);
+        }
+        if let Some(card_count) = s.strip_prefix("draw:") {
+            let card_count = card_count.parse().map_err(|_| ())?;
+            return Ok(Self::Draw { card_count });
+        }
+        if let Some(s) = s.strip_prefix("flip:") {
+            return Ok(Self::Flip { column: column(s)? });
+        }
+
+        let (from, to) = s.split_once('>').ok_or(())?;
+        let (to, card_count) = match to.split_once('x') {
+            Some((to, card_count)) => (to, card_count.parse().map_err(|_| ())?),
+            None => (to, 1),
+        };
+        match (from, to.chars().next()) {
+            ("w", Some('c')) if card_count == 1 => Ok(Self::DeckToColumn {
+                column: column(to)?,
+            }),
+            ("w", Some('f')) if card_count == 1 => Ok(Self::DeckToPile { pile: pile(to)? }),
+            (_, Some('c')) if from.starts_with('c') => Ok(Self::ColumnToColumn {
+                from: column(from)?,
+                to: column(to)?,
+                card_count,
+            }),
+            (_, Some('f')) if from.starts_with('c') && card_count == 1 => Ok(Self::ColumnToPile {
+                column: column(from)?,
+                pile: pile(to)?,
+            }),
+            (_, Some('c')) if from.starts_with('f') && card_count == 1 => Ok(Self::PileToColumn {
+                pile: pile(from)?,
+                column: column(to)?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Move `card_count` selected cards, leaving both collections unchanged on failure
+fn transfer(
+    from: Selection,
+    to: Selection,
+    card_count: usize,
+    game_state: &mut GameState,
+) -> Result<(), MoveError> {
+    if from.same_collection(to) {
+        return Err(MoveError::NoSuchMove);
+    }
+    // Check first, so that `take` can't drop cards on a partial failure
+    to.selected_collection(game_state)?;
+    from.selected_collection(game_state)?
+        .peek_n(card_count)
+        .ok_or(MoveError::EmptySource)?;
+
+    let cards = from.selected_collection(game_state)?.take(card_count)?;
+    to.selected_collection(game_state)?.receive(cards)
+}
+
+/// Move cards one at a time from the top of `from` to the top of `to`
+fn move_stock_cards(
+    from: &mut Vec<Card>,
+    to: &mut Vec<Card>,
+    card_count: usize,
+) -> Result<(), MoveError> {
+    if card_count == 0 {
+        return Err(MoveError::WrongDrawCount);
+    }
+    if from.len() < card_count {
+        return Err(MoveError::EmptySource);
+    }
+    for _ in 0..card_count {
+        to.push(from.pop().expect("card count should have been checked"));
+    }
+    Ok(())
+}
+
+/// Turn over the whole `from` pile onto the empty `to` pile
+fn turn_over(from: &mut Vec<Card>, to: &mut Vec<Card>) -> Result<(), MoveError> {
+    if from.is_empty() {
+        return Err(MoveError::EmptySource);
+    }
+    if !to.is_empty() {
+        return Err(MoveError::StockNotEmpty);
+    }
+    to.extend(from.drain(..).rev());
+    Ok(())
+}
+
+fn set_last_card_state(
+    game_state: &mut GameState,
+    column: usize,
+    from: CardState,
+    to: CardState,
+) -> Result<(), MoveError> {
+    let (_, card_state) = game_state
+        .columns
+        .get_mut(column)
+        .ok_or(MoveError::NoSuchColumn)?
+        .0
+        .last_mut()
+        .ok_or(MoveError::EmptySource)?;
+    if *card_state != from {
+        return Err(match from {
+            CardState::FaceDown => MoveError::NotFaceDown,
+            CardState::FaceUp => MoveError::FaceDown,
+        });
+    }
+    *card_state = to;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic;
+
+    /// Apply then revert `mv`, checking that the original state is restored
+    fn apply_and_revert(mv: Move, game_state: &GameState) -> GameState {
+        let mut a = game_state.clone();
+        mv.apply(&mut a).unwrap();
+        assert_ne!(&a, game_state);
+        let applied = a.clone();
+        mv.revert(&mut a).unwrap();
+        assert_eq!(&a, game_state);
+        applied
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn test_selections_round_trip() {
+        let moves = [
+            Move::ColumnToColumn {
+                from: 1,
+                to: 2,
+                card_count: 3,
+            },
+            Move::ColumnToPile { column: 4, pile: 1 },
+            Move::DeckToColumn { column: 6 },
+            Move::DeckToPile { pile: 3 },
+            Move::PileToColumn { pile: 2, column: 0 },
+        ];
+        for mv in moves {
+            let (from, to) = mv.selections().unwrap();
+            assert_eq!(Move::from_selections(from, to), Some(mv));
+        }
+        assert_eq!(Move::Recycle.selections(), None);
+        assert_eq!(
+            Move::from_selections(Selection::Pile { index: 0 }, Selection::Pile { index: 1 }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_notation() {
+        let moves = [
+            (
+                Move::ColumnToColumn {
+                    from: 1,
+                    to: 4,
+                    card_count: 3,
+                },
+                "c2>c5x3",
+            ),
+            (
+                Move::ColumnToColumn {
+                    from: 6,
+                    to: 0,
+                    card_count: 1,
+                },
+                "c7>c1",
+            ),
+            (Move::ColumnToPile { column: 3, pile: 0 }, "c4>f1"),
+            (Move::DeckToColumn { column: 2 }, "w>c3"),
+            (Move::DeckToPile { pile: 3 }, "w>f4"),
+            (Move::PileToColumn { pile: 1, column: 5 }, "f2>c6"),
+            (Move::Draw { card_count: 3 }, "draw:3"),
+            (Move::Recycle, "recycle"),
+            (Move::Flip { column: 6 }, "flip:c7"),
+        ];
+        for (mv, text) in moves {
+            assert_eq!(mv.to_string(), text);
+            assert_eq!(text.parse(), Ok(mv));
+        }
+
+        for text in [
+            "", "c0>c1", "c8>c1", "c1>f5", "w>c1x2", "c1>f1x2", "f1>f2", "w>w", "flip:f1",
+        ] {
+            assert_eq!(text.parse::<Move>(), Err(()), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_card_moves() {
+        let mut a = GameState::init(Card::ordered_deck());
+        game_logic::face_up_on_columns(&mut a);
+
+        let b = apply_and_revert(
+            Move::ColumnToColumn {
+                from: 6,
+                to: 0,
+                card_count: 1,
+            },
+            &a,
+        );
+        assert_eq!(6, b.columns[6].0.len());
+        assert_eq!(2, b.columns[0].0.len());
+
+        let b = apply_and_revert(Move::ColumnToPile { column: 3, pile: 0 }, &a);
+        assert_eq!(1, b.card_piles[0].0.len());
+
+        a.deck_drawn.push(a.deck.pop().unwrap());
+        let b = apply_and_revert(Move::DeckToColumn { column: 2 }, &a);
+        assert_eq!(4, b.columns[2].0.len());
+        let b = apply_and_revert(Move::DeckToPile { pile: 1 }, &a);
+        assert_eq!(1, b.card_piles[1].0.len());
+
+        a.card_piles[2].0.push(a.deck.pop().unwrap());
+        apply_and_revert(Move::PileToColumn { pile: 2, column: 5 }, &a);
+    }
+
+    #[test]
+    fn test_stock_moves() {
+        let a = GameState::init(Card::ordered_deck());
+
+        let b = apply_and_revert(Move::Draw { card_count: 3 }, &a);
+        assert_eq!(a.deck[a.deck.len() - 3..], {
+            let mut drawn = b.deck_drawn.clone();
+            drawn.reverse();
+            drawn
+        });
+        assert!(Move::Draw { card_count: 25 }.apply(&mut a.clone()).is_err());
+        assert!(Move::Recycle.apply(&mut a.clone()).is_err());
+
+        let mut c = a.clone();
+        Move::Draw { card_count: 24 }.apply(&mut c).unwrap();
+        let d = apply_and_revert(Move::Recycle, &c);
+        assert_eq!(a.deck, d.deck);
+    }
+
+    #[test]
+    fn test_flip() {
+        let a = GameState::init(Card::ordered_deck());
+        let b = apply_and_revert(Move::Flip { column: 4 }, &a);
+        assert_eq!(
+            Some(&CardState::FaceUp),
+            b.columns[4].0.last().map(|(_, s)| s)
+        );
+        assert!(Move::Flip { column: 4 }.apply(&mut b.clone()).is_err());
+    }
+}
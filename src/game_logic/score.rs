@@ -0,0 +1,205 @@
+//! Scores games from the moves played.
+
+use super::Move;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Scoring {
+    Off,
+    /// Windows-style: points for progress, penalties for going backwards
+    #[default]
+    Standard,
+    /// Dollars: each deal costs $52, and each foundation card pays $5.
+    /// (The stock can be passed through only once in Draw One, or 3 times when drawing more)
+    Vegas,
+}
+
+impl Scoring {
+    pub const NAMES: &'static str = "off, standard, vegas";
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Standard => "Standard",
+            Self::Vegas => "Vegas",
+        }
+    }
+
+    /// The next scoring option, for cycling through them
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Standard,
+            Self::Standard => Self::Vegas,
+            Self::Vegas => Self::Off,
+        }
+    }
+
+    /// Maximum number of passes through the stock (unlimited if None)
+    pub fn pass_limit(self, draw_count: usize) -> Option<usize> {
+        match (self, draw_count) {
+            (Self::Vegas, 1) => Some(1),
+            (Self::Vegas, _) => Some(3),
+            _ => None,
+        }
+    }
+
+    /// A score as text, e.g.: "120", or "-$52" for Vegas
+    pub fn format(self, score: i32) -> String {
+        match self {
+            Self::Vegas if score < 0 => format!("-${}", -score),
+            Self::Vegas => format!("${score}"),
+            _ => score.to_string(),
+        }
+    }
+}
+
+impl FromStr for Scoring {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "standard" => Ok(Self::Standard),
+            "vegas" => Ok(Self::Vegas),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Dollars for one move in Vegas scoring
+fn dollars(mv: Move) -> i32 {
+    match mv {
+        Move::DeckToPile { .. } | Move::ColumnToPile { .. } => 5,
+        Move::PileToColumn { .. } => -5,
+        _ => 0,
+    }
+}
+
+/// Points for one move in Standard scoring
+fn points(mv: Move, draw_count: usize) -> i32 {
+    match mv {
+        Move::DeckToColumn { .. } => 5,
+        Move::DeckToPile { .. } | Move::ColumnToPile { .. } => 10,
+        Move::Flip { .. } => 5,
+        Move::PileToColumn { .. } => -15,
+        Move::Recycle if draw_count == 1 => -100,
+        _ => 0,
+    }
+}
+
+/// The score for a game's moves (so far), or None if scoring is off
+pub fn score<'a>(
+    scoring: Scoring,
+    draw_count: usize,
+    moves: impl IntoIterator<Item = &'a Move>,
+) -> Option<i32> {
+    match scoring {
+        Scoring::Off => None,
+        // (The score never goes below zero)
+        Scoring::Standard => Some(
+            moves
+                .into_iter()
+                .fold(0, |score, &mv| (score + points(mv, draw_count)).max(0)),
+        ),
+        Scoring::Vegas => Some(
+            moves
+                .into_iter()
+                .fold(-52, |score, &mv| score + dollars(mv)),
+        ),
+    }
+}
+
+/// The penalties among a game's moves (as a negative score), which undoing them doesn't take back
+pub fn penalties<'a>(
+    scoring: Scoring,
+    draw_count: usize,
+    moves: impl IntoIterator<Item = &'a Move>,
+) -> i32 {
+    let value = |mv| match scoring {
+        Scoring::Off => 0,
+        Scoring::Standard => points(mv, draw_count),
+        Scoring::Vegas => dollars(mv),
+    };
+    moves.into_iter().map(|&mv| value(mv).min(0)).sum()
+}
+
+/// Bonus points for winning a game in `time`
+pub fn time_bonus(scoring: Scoring, time: Duration) -> i32 {
+    match scoring {
+        Scoring::Off | Scoring::Vegas => 0,
+        // (No bonus for games under 30 seconds)
+        Scoring::Standard => match time.as_secs() {
+            seconds @ 30.. => (700_000 / seconds) as i32,
+            _ => 0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let moves = [
+            Move::Draw { card_count: 1 },
+            Move::DeckToColumn { column: 0 },
+            Move::Flip { column: 1 },
+            Move::ColumnToPile { column: 1, pile: 0 },
+            Move::DeckToPile { pile: 1 },
+        ];
+        assert_eq!(score(Scoring::Standard, 1, &moves), Some(30));
+        assert_eq!(score(Scoring::Off, 1, &moves), None);
+
+        // Never below zero
+        let moves = [Move::Recycle, Move::Flip { column: 0 }];
+        assert_eq!(score(Scoring::Standard, 1, &moves), Some(5));
+        assert_eq!(score(Scoring::Standard, 3, &moves), Some(5));
+        let moves = [
+            Move::DeckToPile { pile: 0 },
+            Move::PileToColumn { pile: 0, column: 0 },
+        ];
+        assert_eq!(score(Scoring::Standard, 1, &moves), Some(0));
+    }
+
+    #[test]
+    fn test_vegas_score() {
+        let moves = [
+            Move::DeckToPile { pile: 0 },
+            Move::ColumnToPile { column: 1, pile: 0 },
+            Move::PileToColumn { pile: 0, column: 2 },
+            Move::Flip { column: 1 },
+        ];
+        let score = score(Scoring::Vegas, 3, &moves).unwrap();
+        assert_eq!(score, -47);
+        assert_eq!(Scoring::Vegas.format(score), "-$47");
+        assert_eq!(Scoring::Vegas.format(208), "$208");
+        assert_eq!(Scoring::Standard.format(208), "208");
+
+        assert_eq!(Scoring::Vegas.pass_limit(1), Some(1));
+        assert_eq!(Scoring::Vegas.pass_limit(3), Some(3));
+        assert_eq!(Scoring::Standard.pass_limit(1), None);
+    }
+
+    #[test]
+    fn test_penalties() {
+        let moves = [
+            Move::Recycle,
+            Move::DeckToPile { pile: 0 },
+            Move::PileToColumn { pile: 0, column: 0 },
+        ];
+        assert_eq!(penalties(Scoring::Standard, 1, &moves), -115);
+        assert_eq!(penalties(Scoring::Standard, 3, &moves), -15);
+        assert_eq!(penalties(Scoring::Vegas, 1, &moves), -5);
+        assert_eq!(penalties(Scoring::Off, 1, &moves), 0);
+    }
+
+    #[test]
+    fn test_time_bonus() {
+        let bonus = |seconds| time_bonus(Scoring::Standard, Duration::from_secs(seconds));
+        assert_eq!(bonus(10), 0);
+        assert_eq!(bonus(100), 7000);
+        assert_eq!(time_bonus(Scoring::Off, Duration::from_secs(100)), 0);
+    }
+}
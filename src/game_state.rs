@@ -1,54 +1,55 @@
+mod rules;
+mod text;
+
+pub use rules::{EmptyColumn, Rules};
+
 use crate::cards::{Card, Rank, Suit};
+use crate::game_logic::MoveError;
 use strum::IntoEnumIterator;
 
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum CardState {
     #[default]
     FaceUp,
     FaceDown,
 }
 
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct CardColumn(pub Vec<(Card, CardState)>);
 
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct CardPile(pub Vec<Card>);
 
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
-pub enum GameMode {
-    #[default]
-    DrawOne,
-    DrawThree,
-}
-
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct GameState {
-    pub game_mode: GameMode,
+    pub rules: Rules,
     pub deck: Vec<Card>,
     pub deck_drawn: Vec<Card>,
     pub columns: [CardColumn; Self::COLUMN_COUNT],
     pub card_piles: [CardPile; Self::CARD_PILES_COUNT],
+    /// Number of times the drawn pile has been turned over into the stock
+    pub recycle_count: usize,
 }
 
 pub trait CardCollection {
-    fn take(&mut self, cards_count: usize) -> Result<Vec<Card>, ()>;
-    fn receive(&mut self, cards: Vec<Card>) -> Result<(), ()>;
+    fn take(&mut self, cards_count: usize) -> Result<Vec<Card>, MoveError>;
+    fn receive(&mut self, cards: Vec<Card>) -> Result<(), MoveError>;
     fn peek(&self) -> Option<Card>;
     fn peek_n(&self, count: usize) -> Option<Vec<Card>>;
 }
 
 impl CardCollection for CardColumn {
-    fn take(&mut self, count: usize) -> Result<Vec<Card>, ()> {
+    fn take(&mut self, count: usize) -> Result<Vec<Card>, MoveError> {
         let mut v = vec![];
         for _ in 0..count {
-            let val = self.0.pop().ok_or(())?;
+            let val = self.0.pop().ok_or(MoveError::EmptySource)?;
             v.push(val.0);
         }
         v.reverse();
 
         Ok(v)
     }
-    fn receive(&mut self, cards: Vec<Card>) -> Result<(), ()> {
+    fn receive(&mut self, cards: Vec<Card>) -> Result<(), MoveError> {
         for card in cards {
             self.0.push((card, CardState::FaceUp));
         }
@@ -65,22 +66,22 @@ impl CardCollection for CardColumn {
 }
 
 impl CardCollection for CardPile {
-    fn take(&mut self, count: usize) -> Result<Vec<Card>, ()> {
+    fn take(&mut self, count: usize) -> Result<Vec<Card>, MoveError> {
         let mut v = vec![];
         match count {
             1 => {
-                let val = self.0.pop().ok_or(())?;
+                let val = self.0.pop().ok_or(MoveError::EmptySource)?;
                 v.push(val);
             }
-            _ => return Err(()),
+            _ => return Err(MoveError::MultipleCards),
         }
         v.reverse();
 
         Ok(v)
     }
-    fn receive(&mut self, cards: Vec<Card>) -> Result<(), ()> {
+    fn receive(&mut self, cards: Vec<Card>) -> Result<(), MoveError> {
         if cards.len() != 1 {
-            return Err(());
+            return Err(MoveError::MultipleCards);
         }
         self.0.push(cards[0]);
         Ok(())
@@ -95,22 +96,22 @@ impl CardCollection for CardPile {
 }
 
 impl CardCollection for Vec<Card> {
-    fn take(&mut self, count: usize) -> Result<Vec<Card>, ()> {
+    fn take(&mut self, count: usize) -> Result<Vec<Card>, MoveError> {
         let mut v = vec![];
         match count {
             1 => {
-                let val = self.pop().ok_or(())?;
+                let val = self.pop().ok_or(MoveError::EmptySource)?;
                 v.push(val);
             }
-            _ => return Err(()),
+            _ => return Err(MoveError::MultipleCards),
         }
         v.reverse();
 
         Ok(v)
     }
-    fn receive(&mut self, cards: Vec<Card>) -> Result<(), ()> {
+    fn receive(&mut self, cards: Vec<Card>) -> Result<(), MoveError> {
         if cards.len() != 1 {
-            return Err(());
+            return Err(MoveError::MultipleCards);
         }
         self.push(cards[0]);
         Ok(())
@@ -152,38 +153,30 @@ impl GameState {
         }
 
         Self {
-            game_mode: Default::default(),
+            rules: Default::default(),
             deck,
             deck_drawn: Default::default(),
             columns,
             card_piles,
+            recycle_count: 0,
         }
     }
 
-    pub fn deck_hit(&mut self) {
-        if self.deck.is_empty() && !self.deck_drawn.is_empty() {
-            self.deck = self.deck_drawn.clone();
-            self.deck.reverse();
-            self.deck_drawn.clear();
-        }
-
-        let count = match self.game_mode {
-            GameMode::DrawOne => 1,
-            GameMode::DrawThree => 3,
-        };
+    /// The current pass through the stock, starting from 1
+    pub fn pass(&self) -> usize {
+        self.recycle_count + 1
+    }
 
-        for _ in 0..count {
-            if let Some(card) = self.deck.pop() {
-                self.deck_drawn.push(card);
-            }
-        }
+    /// Is another pass through the stock allowed?
+    pub fn can_recycle(&self) -> bool {
+        self.rules
+            .pass_limit
+            .is_none_or(|pass_limit| self.pass() < pass_limit)
     }
 
-    #[allow(dead_code)]
-    pub fn auto_hit(&mut self) {
-        if !self.deck.is_empty() && self.deck_drawn.is_empty() {
-            self.deck_hit();
-        }
+    /// Number of cards drawn from the stock per hit
+    pub fn draw_count(&self) -> usize {
+        self.rules.draw_count
     }
 
     #[allow(dead_code)]
@@ -227,6 +220,16 @@ mod tests {
         assert_eq!(24, a.deck.len());
     }
 
+    #[test]
+    fn test_pass_limit() {
+        let mut a = GameState::default();
+        a.rules.pass_limit = Some(2);
+        assert!(a.can_recycle());
+        a.recycle_count = 1;
+        assert_eq!(a.pass(), 2);
+        assert!(!a.can_recycle());
+    }
+
     #[test]
     fn test_card_collection_send_to() {
         let mut a = GameState::init(Card::ordered_deck());
@@ -0,0 +1,204 @@
+//! Game rule options, fixed for the whole of a game.
+//!
+//! Text format (one line per rule; only "draw" is written when the others are defaults):
+//! ```text
+//! draw 3
+//! passes 3
+//! empty_column any
+//! foundation_to_tableau no
+//! auto_flip off
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Which cards may be moved to an empty column
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EmptyColumn {
+    #[default]
+    KingsOnly,
+    AnyCard,
+}
+
+impl EmptyColumn {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::KingsOnly => "Kings only",
+            Self::AnyCard => "Any card",
+        }
+    }
+
+    /// The other option, for toggling
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::KingsOnly => Self::AnyCard,
+            Self::AnyCard => Self::KingsOnly,
+        }
+    }
+}
+
+impl FromStr for EmptyColumn {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "kings" => Ok(Self::KingsOnly),
+            "any" => Ok(Self::AnyCard),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rules {
+    /// Number of cards drawn from the stock per hit
+    pub draw_count: usize,
+    /// Maximum number of passes through the stock (unlimited if None)
+    pub pass_limit: Option<usize>,
+    pub empty_column: EmptyColumn,
+    /// Can cards be moved from the foundations back to the tableau columns?
+    pub foundation_to_tableau: bool,
+    /// Are uncovered face-down column cards turned up automatically?
+    /// (If not, the player turns them up with Enter)
+    pub auto_flip: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            draw_count: 1,
+            pass_limit: None,
+            empty_column: EmptyColumn::default(),
+            foundation_to_tableau: true,
+            auto_flip: true,
+        }
+    }
+}
+
+impl Rules {
+    /// Parse a draw count: a number of cards, at least 1
+    pub fn parse_draw_count(s: &str) -> Result<usize, &'static str> {
+        s.parse()
+            .ok()
+            .filter(|&count| count > 0)
+            .ok_or("draw count should be at least 1")
+    }
+
+    /// Parse a pass limit: a number of passes, or "unlimited"
+    pub fn parse_pass_limit(s: &str) -> Result<Option<usize>, &'static str> {
+        match s {
+            "unlimited" => Ok(None),
+            _ => s
+                .parse()
+                .ok()
+                .filter(|&passes| passes > 0)
+                .map(Some)
+                .ok_or("invalid pass limit"),
+        }
+    }
+
+    pub fn format_pass_limit(pass_limit: Option<usize>) -> String {
+        pass_limit.map_or("unlimited".to_string(), |passes| passes.to_string())
+    }
+
+    /// Apply one line of the text format to these rules
+    /// Returns: None if the line isn't a rule, or else a description of any error
+    pub fn parse_line(&mut self, line: &str) -> Option<Result<(), String>> {
+        let (key, value) = line.split_once(' ')?;
+        let value = value.trim();
+        let result = match key {
+            "draw" => Self::parse_draw_count(value).map(|draw_count| self.draw_count = draw_count),
+            "passes" => {
+                Self::parse_pass_limit(value).map(|pass_limit| self.pass_limit = pass_limit)
+            }
+            "empty_column" => value
+                .parse()
+                .map(|empty_column| self.empty_column = empty_column)
+                .map_err(|_| "empty_column should be kings or any"),
+            "foundation_to_tableau" => parse_switch(value, "yes", "no")
+                .map(|allowed| self.foundation_to_tableau = allowed)
+                .map_err(|_| "foundation_to_tableau should be yes or no"),
+            "auto_flip" => parse_switch(value, "on", "off")
+                .map(|auto_flip| self.auto_flip = auto_flip)
+                .map_err(|_| "auto_flip should be on or off"),
+            _ => return None,
+        };
+        Some(result.map_err(str::to_string))
+    }
+}
+
+fn parse_switch(value: &str, on: &str, off: &str) -> Result<bool, ()> {
+    if value == on {
+        Ok(true)
+    } else if value == off {
+        Ok(false)
+    } else {
+        Err(())
+    }
+}
+
+impl Display for Rules {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let defaults = Self::default();
+        writeln!(f, "draw {}", self.draw_count)?;
+        if self.pass_limit != defaults.pass_limit {
+            writeln!(f, "passes {}", Self::format_pass_limit(self.pass_limit))?;
+        }
+        if self.empty_column != defaults.empty_column {
+            writeln!(f, "empty_column any")?;
+        }
+        if self.foundation_to_tableau != defaults.foundation_to_tableau {
+            writeln!(f, "foundation_to_tableau no")?;
+        }
+        if self.auto_flip != defaults.auto_flip {
+            writeln!(f, "auto_flip off")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_text() {
+        assert_eq!(Rules::default().to_string(), "draw 1\n");
+
+        let rules = Rules {
+            draw_count: 3,
+            pass_limit: Some(3),
+            empty_column: EmptyColumn::AnyCard,
+            foundation_to_tableau: false,
+            auto_flip: false,
+        };
+        let text = rules.to_string();
+        assert_eq!(
+            text,
+            "draw 3\npasses 3\nempty_column any\nfoundation_to_tableau no\nauto_flip off\n"
+        );
+        let mut parsed = Rules::default();
+        for line in text.lines() {
+            assert_eq!(parsed.parse_line(line), Some(Ok(())));
+        }
+        assert_eq!(parsed, rules);
+
+        assert_eq!(parsed.parse_line("c1: AH"), None);
+        assert_eq!(
+            parsed.parse_line("draw 0"),
+            Some(Err("draw count should be at least 1".to_string()))
+        );
+        assert_eq!(
+            parsed.parse_line("auto_flip yes"),
+            Some(Err("auto_flip should be on or off".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_pass_limit() {
+        assert_eq!(Rules::parse_pass_limit("unlimited"), Ok(None));
+        assert_eq!(Rules::parse_pass_limit("3"), Ok(Some(3)));
+        assert_eq!(Rules::parse_pass_limit("0"), Err("invalid pass limit"));
+        assert_eq!(Rules::format_pass_limit(Some(3)), "3");
+    }
+}
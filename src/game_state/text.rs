@@ -0,0 +1,204 @@
+//! Text format for a game position, e.g.: for bug reports and test fixtures.
+//!
+//! ```text
+//! draw 3
+//! passes 3
+//! pass 2
+//! stock: 9♣ 7♣ Q♦
+//! waste: 2♥ 3♥
+//! c1: [K♠] [2♦] 5♥
+//! c2:
+//! ...
+//! c7: [A♦] JS
+//! f1: A♥ 2♥
+//! f2:
+//! f3:
+//! f4:
+//! ```
+//! The game rules come first (see `Rules`), then the current pass through the stock.
+//! Cards are listed bottom to top (the last one is on top).
+//! Face-down column cards are in brackets; stock cards are all face-down.
+//! Any missing line means an empty collection, and `#` starts a comment line.
+
+use super::{CardColumn, CardState, GameState};
+use crate::cards::Card;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+fn write_cards<'a>(
+    f: &mut Formatter<'_>,
+    label: &str,
+    cards: impl IntoIterator<Item = &'a Card>,
+) -> std::fmt::Result {
+    write!(f, "{label}:")?;
+    for card in cards {
+        write!(f, " {card}")?;
+    }
+    writeln!(f)
+}
+
+impl Display for GameState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rules)?;
+        writeln!(f, "pass {}", self.pass())?;
+        write_cards(f, "stock", &self.deck)?;
+        write_cards(f, "waste", &self.deck_drawn)?;
+        for (index, column) in self.columns.iter().enumerate() {
+            write!(f, "c{}:", index + 1)?;
+            for (card, card_state) in &column.0 {
+                match card_state {
+                    CardState::FaceUp => write!(f, " {card}")?,
+                    CardState::FaceDown => write!(f, " [{card}]")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        for (index, pile) in self.card_piles.iter().enumerate() {
+            write_cards(f, &format!("f{}", index + 1), &pile.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameState {
+    /// A description of the first error
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut game_state = Self::default();
+        let mut seen = HashSet::new();
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: &str| format!("line {}: {message}: {line}", line_index + 1);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(result) = game_state.rules.parse_line(line) {
+                result.map_err(|message| error(&message))?;
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("pass ") {
+                game_state.recycle_count = value
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|pass| pass.checked_sub(1))
+                    .ok_or_else(|| error("invalid pass"))?;
+                continue;
+            }
+
+            let (label, cards) = line.split_once(':').ok_or_else(|| error("missing ':'"))?;
+            let mut column = CardColumn::default();
+            for text in cards.split_whitespace() {
+                let (text, card_state) = match text.strip_prefix('[') {
+                    Some(inner) => (
+                        inner
+                            .strip_suffix(']')
+                            .ok_or_else(|| error(&format!("missing ']' in \"{text}\"")))?,
+                        CardState::FaceDown,
+                    ),
+                    None => (text, CardState::FaceUp),
+                };
+                let card: Card = text
+                    .parse()
+                    .map_err(|_| error(&format!("invalid card \"{text}\"")))?;
+                if !seen.insert(card) {
+                    return Err(error(&format!("{card} is listed twice")));
+                }
+                column.0.push((card, card_state));
+            }
+
+            let index = |prefix: char, count: usize| {
+                label
+                    .strip_prefix(prefix)?
+                    .parse::<usize>()
+                    .ok()?
+                    .checked_sub(1)
+                    .filter(|&index| index < count)
+            };
+            if let Some(index) = index('c', Self::COLUMN_COUNT) {
+                game_state.columns[index] = column;
+                continue;
+            }
+            if column.face_up_cards() != column.0.len() {
+                return Err(error("only column cards can be face-down"));
+            }
+            let cards = column.0.into_iter().map(|(card, _)| card).collect();
+            match label {
+                "stock" => game_state.deck = cards,
+                "waste" => game_state.deck_drawn = cards,
+                _ => match index('f', Self::CARD_PILES_COUNT) {
+                    Some(index) => game_state.card_piles[index].0 = cards,
+                    None => return Err(error(&format!("unknown label \"{label}\""))),
+                },
+            }
+        }
+
+        Ok(game_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+
+    #[test]
+    fn test_game_state_text() {
+        let mut a = GameState::init(Card::seeded_deck(3));
+        a.rules.draw_count = 3;
+        a.rules.pass_limit = Some(3);
+        a.rules.auto_flip = false;
+        crate::game_logic::face_up_on_columns(&mut a);
+        crate::game_logic::deck_hit(&mut a);
+        a.recycle_count = 1;
+        assert!(a
+            .to_string()
+            .starts_with("draw 3\npasses 3\nauto_flip off\npass 2\n"));
+        assert_eq!(a.to_string().parse(), Ok(a.clone()));
+
+        let a = GameState::almost_victory();
+        let text = a.to_string();
+        assert!(text.starts_with("draw 1\npass 1\nstock:\nwaste:\nc1: K♥\nc2:\n"));
+        assert!(text.ends_with("f4: A♣ 2♣ 3♣ 4♣ 5♣ 6♣ 7♣ 8♣ 9♣ 10♣ J♣ Q♣ K♣\n"));
+        assert_eq!(text.parse(), Ok(a));
+    }
+
+    #[test]
+    fn test_game_state_from_str() {
+        let a: GameState = "# fixture\nc3: [TH] 9s\nf2: AC\n".parse().unwrap();
+        assert_eq!(
+            a.columns[2].0,
+            vec![
+                (Card::new(Suit::Hearts, Rank::R10), CardState::FaceDown),
+                (Card::new(Suit::Spades, Rank::R9), CardState::FaceUp),
+            ]
+        );
+        assert_eq!(a.card_piles[1].0, vec![Card::new(Suit::Clubs, Rank::Ace)]);
+        assert!(a.deck.is_empty());
+
+        assert_eq!(
+            "c1: AH\nf1: AH".parse::<GameState>(),
+            Err("line 2: A♥ is listed twice: f1: AH".to_string())
+        );
+        assert_eq!(
+            "stock: [AH]".parse::<GameState>(),
+            Err("line 1: only column cards can be face-down: stock: [AH]".to_string())
+        );
+        assert_eq!(
+            "c8: AH".parse::<GameState>(),
+            Err("line 1: unknown label \"c8\": c8: AH".to_string())
+        );
+        assert_eq!(
+            "draw 0".parse::<GameState>(),
+            Err("line 1: draw count should be at least 1: draw 0".to_string())
+        );
+        assert_eq!(
+            "c1: [AH".parse::<GameState>(),
+            Err("line 1: missing ']' in \"[AH\": c1: [AH".to_string())
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Undo/redo history of game moves.
+
+use crate::game_logic::Move;
+use crate::game_state::GameState;
+
+/// The moves made in each turn, so that a turn's automatic moves
+/// (e.g.: face-up flips) are undone & redone along with it
+#[derive(Debug, Default, Clone)]
+pub struct History {
+    undo_stack: Vec<Vec<Move>>,
+    redo_stack: Vec<Vec<Move>>,
+}
+
+impl History {
+    /// Record the moves applied in the latest turn.
+    /// (Any undone turns can no longer be redone.)
+    pub fn record(&mut self, moves: Vec<Move>) {
+        if moves.is_empty() {
+            return;
+        }
+        self.undo_stack.push(moves);
+        self.redo_stack.clear();
+    }
+
+    /// Revert the latest turn
+    /// Returns: true IFF `game_state` has changed
+    pub fn undo(&mut self, game_state: &mut GameState) -> bool {
+        if let Some(moves) = self.undo_stack.pop() {
+            for mv in moves.iter().rev() {
+                mv.revert(game_state)
+                    .expect("recorded move should be revertible");
+            }
+            self.redo_stack.push(moves);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reapply the latest undone turn
+    /// Returns: true IFF `game_state` has changed
+    pub fn redo(&mut self, game_state: &mut GameState) -> bool {
+        if let Some(moves) = self.redo_stack.pop() {
+            for mv in &moves {
+                mv.apply(game_state)
+                    .expect("undone move should be reapplicable");
+            }
+            self.undo_stack.push(moves);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The moves of each turn that can be undone, oldest first
+    pub fn turns(&self) -> &[Vec<Move>] {
+        &self.undo_stack
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::game_logic;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::default();
+        let mut game_state = GameState::init(Card::ordered_deck());
+        game_logic::face_up_on_columns(&mut game_state);
+        let initial = game_state.clone();
+
+        assert!(!history.undo(&mut game_state));
+
+        let mut moves = game_logic::deck_hit(&mut game_state);
+        let mv = Move::ColumnToColumn {
+            from: 6,
+            to: 0,
+            card_count: 1,
+        };
+        mv.apply(&mut game_state).unwrap();
+        moves.push(mv);
+        moves.extend(game_logic::face_up_on_columns(&mut game_state));
+        history.record(moves);
+        let after_turn = game_state.clone();
+
+        assert!(history.undo(&mut game_state));
+        assert_eq!(initial, game_state);
+        assert!(!history.undo(&mut game_state));
+
+        assert!(history.redo(&mut game_state));
+        assert_eq!(after_turn, game_state);
+        assert!(!history.redo(&mut game_state));
+    }
+
+    #[test]
+    fn test_record_clears_redo() {
+        let mut history = History::default();
+        let mut game_state = GameState::init(Card::ordered_deck());
+
+        history.record(game_logic::deck_hit(&mut game_state));
+        assert!(history.undo(&mut game_state));
+
+        history.record(game_logic::deck_hit(&mut game_state));
+        assert!(!history.redo(&mut game_state));
+    }
+}
@@ -0,0 +1,147 @@
+//! Sources of input events: the terminal, or a script (e.g.: a recorded input file).
+//!
+//! Input file format: events separated by whitespace, where an event is either a key,
+//! or Resize (the terminal size changed). A key is either a single character, or one of:
+//! Enter, Space, Tab, Esc, Backspace, Left, Right, Up, Down, Home, End, Ctrl-<char>.
+//! A line starting with `#` is a comment, e.g.:
+//! ```text
+//! # new game, then hit twice
+//! 1 Enter Enter
+//! ```
+
+use signal_hook::consts::SIGWINCH;
+use signal_hook::iterator::Signals;
+use std::io::stdin;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use termion::input::TermRead;
+
+pub use termion::event::Key;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Event {
+    Key(Key),
+    /// The terminal size changed
+    Resize,
+}
+
+/// Start reading key presses from stdin and watching for terminal resizes, on other threads
+pub fn terminal_events() -> Receiver<Event> {
+    let (sender, receiver) = mpsc::channel();
+    let resize_sender = sender.clone();
+    thread::spawn(move || {
+        for key in stdin().keys() {
+            // (Stop on a read error, or if the Ui is gone)
+            let Ok(key) = key else { break };
+            if sender.send(Event::Key(key)).is_err() {
+                break;
+            }
+        }
+    });
+    // (Without the signal, the layout just stays as it is)
+    if let Ok(mut signals) = Signals::new([SIGWINCH]) {
+        thread::spawn(move || {
+            for _ in signals.forever() {
+                if resize_sender.send(Event::Resize).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    receiver
+}
+
+/// Events from a list, which then run out (the Ui treats that like Ctrl-c)
+pub fn scripted_events(events: impl IntoIterator<Item = Event>) -> Receiver<Event> {
+    let (sender, receiver) = mpsc::channel();
+    for event in events {
+        sender.send(event).expect("receiver should exist");
+    }
+    receiver
+}
+
+/// Parse the input file format
+/// Returns: the events, or a description of the first error
+pub fn parse_events(text: &str) -> Result<Vec<Event>, String> {
+    let mut events = vec![];
+    for (index, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        for word in line.split_whitespace() {
+            let event = match word {
+                "Resize" => Some(Event::Resize),
+                _ => parse_key(word).map(Event::Key),
+            };
+            let event =
+                event.ok_or_else(|| format!("line {}: unknown event: {word}", index + 1))?;
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+fn parse_key(word: &str) -> Option<Key> {
+    let mut chars = word.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(c));
+    }
+    if let Some(c) = word.strip_prefix("Ctrl-") {
+        let mut chars = c.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(Key::Ctrl(c)),
+            _ => None,
+        };
+    }
+    Some(match word {
+        "Enter" => Key::Char('\n'),
+        "Space" => Key::Char(' '),
+        "Tab" => Key::Char('\t'),
+        "Esc" => Key::Esc,
+        "Backspace" => Key::Backspace,
+        "Left" => Key::Left,
+        "Right" => Key::Right,
+        "Up" => Key::Up,
+        "Down" => Key::Down,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_events() {
+        let text = "# comment\n1 Enter  Space\nLeft Ctrl-c x\n\nResize Esc";
+        let keys = [
+            Key::Char('1'),
+            Key::Char('\n'),
+            Key::Char(' '),
+            Key::Left,
+            Key::Ctrl('c'),
+            Key::Char('x'),
+        ];
+        let mut events: Vec<Event> = keys.into_iter().map(Event::Key).collect();
+        events.extend([Event::Resize, Event::Key(Key::Esc)]);
+        assert_eq!(parse_events(text), Ok(events));
+        assert_eq!(
+            parse_events("1\nEnter Return"),
+            Err("line 2: unknown event: Return".to_string())
+        );
+        assert_eq!(
+            parse_events("Ctrl-cc"),
+            Err("line 1: unknown event: Ctrl-cc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_scripted_events() {
+        let events = scripted_events([Event::Key(Key::Up), Event::Resize]);
+        assert_eq!(events.recv(), Ok(Event::Key(Key::Up)));
+        assert_eq!(events.recv(), Ok(Event::Resize));
+        assert!(events.recv().is_err());
+    }
+}
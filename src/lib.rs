@@ -0,0 +1,43 @@
+//! Klondike solitaire: the rules engine, plus (with the `tui` feature) the terminal game.
+//!
+//! The engine modules don't depend on a terminal, e.g.:
+//! ```
+//! use solitext::cards::Card;
+//! use solitext::game_logic::{self, Move};
+//! use solitext::game_state::GameState;
+//!
+//! let mut game_state = GameState::init(Card::seeded_deck(1));
+//! game_logic::face_up_on_columns(&mut game_state);
+//! for mv in game_logic::legal_moves(&game_state) {
+//!     let mut next = game_state.clone();
+//!     game_logic::play(mv, &mut next).expect("legal move should play");
+//! }
+//! assert!(game_logic::play(Move::Recycle, &mut game_state).is_err());
+//! ```
+
+pub mod cards;
+pub mod game_logic;
+pub mod game_state;
+mod selection;
+pub mod solver;
+
+#[cfg(feature = "tui")]
+pub mod cli;
+#[cfg(feature = "tui")]
+mod clock;
+#[cfg(feature = "tui")]
+mod data_files;
+#[cfg(feature = "tui")]
+pub mod draw;
+#[cfg(feature = "tui")]
+mod history;
+#[cfg(feature = "tui")]
+pub mod input;
+#[cfg(feature = "tui")]
+pub mod replay;
+#[cfg(feature = "tui")]
+pub mod save;
+#[cfg(feature = "tui")]
+mod stats;
+#[cfg(feature = "tui")]
+pub mod tui;
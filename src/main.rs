@@ -1,16 +1,81 @@
-use crate::cards::Card;
-use crate::game_state::GameState;
-use crate::tui::Ui;
-
-mod cards;
-mod draw;
-mod game_logic;
-mod game_state;
-mod selection;
-mod tui;
+use solitext::cards::Card;
+use solitext::cli::{self, Command};
+use solitext::draw::Draw;
+use solitext::game_state::GameState;
+use solitext::input::{self, Event};
+use solitext::replay::Replay;
+use solitext::save::SavedGame;
+use solitext::tui::Ui;
+use std::path::Path;
+use std::process::exit;
 
 fn main() {
+    let args = cli::parse(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("solitext: {error}\n\n{}", cli::USAGE);
+        exit(2);
+    });
+
+    let replay = match &args.command {
+        Command::Play => None,
+        Command::Solve => exit(cli::run_solve(&args)),
+        Command::Stats => exit(cli::run_stats()),
+        Command::Replay(path) => Some(read_replay(path)),
+        Command::Help => {
+            print!("{}", cli::USAGE);
+            return;
+        }
+        Command::Version => {
+            println!("solitext {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
+    };
+
+    let saved_game = args.resume.then(read_saved_game);
+
+    let events = match &args.input {
+        Some(path) => input::scripted_events(read_input(path)),
+        None => input::terminal_events(),
+    };
+    let mut ui = Ui::new(&args, events, Draw::new(args.theme, args.ascii));
+    if let Some(replay) = replay {
+        ui.start_replay(replay, args.delay);
+    } else if let Some(saved_game) = saved_game {
+        ui.start_resume(saved_game);
+    }
     let mut game_state = GameState::init(Card::ordered_deck());
-    let mut ui = Ui::new();
     ui.run(&mut game_state);
 }
+
+fn read_replay(path: &Path) -> Replay {
+    std::fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| text.parse())
+        .unwrap_or_else(|error| {
+            eprintln!("solitext: {}: {error}", path.display());
+            exit(1);
+        })
+}
+
+fn read_input(path: &Path) -> Vec<Event> {
+    std::fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| input::parse_events(&text))
+        .unwrap_or_else(|error| {
+            eprintln!("solitext: {}: {error}", path.display());
+            exit(1);
+        })
+}
+
+fn read_saved_game() -> SavedGame {
+    match SavedGame::load() {
+        Ok(Some(saved_game)) => saved_game,
+        Ok(None) => {
+            eprintln!("solitext: no saved game");
+            exit(1);
+        }
+        Err(error) => {
+            eprintln!("solitext: {error}");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! A recorded game: a seeded deal plus the moves played on it.
+//!
+//! Text format:
+//! ```text
+//! # comment
+//! seed 12345
+//! draw 3
+//! c2>c5 w>f1 draw:3 recycle ...
+//! ```
+//! (The rule lines are as in `Rules`)
+
+use crate::cards::Card;
+use crate::game_logic::Move;
+use crate::game_state::{GameState, Rules};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub rules: Rules,
+    pub moves: Vec<Move>,
+}
+
+impl Replay {
+    /// The dealt game, before any moves
+    pub fn initial_state(&self) -> GameState {
+        let mut game_state = GameState::init(Card::seeded_deck(self.seed));
+        game_state.rules = self.rules;
+        game_state
+    }
+}
+
+impl Display for Replay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const MOVES_PER_LINE: usize = 10;
+        writeln!(f, "seed {}", self.seed)?;
+        write!(f, "{}", self.rules)?;
+        for line in self.moves.chunks(MOVES_PER_LINE) {
+            let line: Vec<String> = line.iter().map(Move::to_string).collect();
+            writeln!(f, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Replay {
+    /// A description of the first error
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seed = None;
+        let mut rules = Rules::default();
+        let mut moves = vec![];
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: &str| format!("line {}: {message}: {line}", line_index + 1);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("seed ") {
+                seed = Some(value.trim().parse().map_err(|_| error("invalid seed"))?);
+            } else if let Some(result) = rules.parse_line(line) {
+                result.map_err(|message| error(&message))?;
+            } else {
+                for text in line.split_whitespace() {
+                    moves.push(
+                        text.parse()
+                            .map_err(|_| error(&format!("invalid move \"{text}\"")))?,
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            seed: seed.ok_or("missing seed")?,
+            rules,
+            moves,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_text() {
+        let replay = Replay {
+            seed: 42,
+            rules: Rules {
+                draw_count: 3,
+                ..Default::default()
+            },
+            moves: vec![
+                Move::Draw { card_count: 3 },
+                Move::DeckToPile { pile: 0 },
+                Move::Flip { column: 3 },
+            ],
+        };
+        let text = replay.to_string();
+        assert_eq!(text, "seed 42\ndraw 3\ndraw:3 w>f1 flip:c4\n");
+        assert_eq!(text.parse(), Ok(replay));
+
+        let commented = "# solved\n\nseed 7\n  c1>c2  \nw>f1\n";
+        let replay: Replay = commented.parse().unwrap();
+        assert_eq!(replay.rules, Rules::default());
+        assert_eq!(replay.moves.len(), 2);
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        assert_eq!("draw 1".parse::<Replay>(), Err("missing seed".to_string()));
+        assert_eq!(
+            "seed 1\ndraw 0".parse::<Replay>(),
+            Err("line 2: draw count should be at least 1: draw 0".to_string())
+        );
+        assert_eq!(
+            "seed 1\nc1>c2 oops".parse::<Replay>(),
+            Err("line 2: invalid move \"oops\": c1>c2 oops".to_string())
+        );
+    }
+}
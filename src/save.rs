@@ -0,0 +1,225 @@
+//! A game in progress, saved to disk so that it can be resumed later.
+//!
+//! Text format (one line per turn, so that undo works after resuming):
+//! ```text
+//! seed 12345
+//! draw 3
+//! passes 3
+//! scoring vegas
+//! time 312
+//! counted
+//! penalties -5
+//! draw:3
+//! c2>c5 flip:c2
+//! ```
+//! (The rule lines are as in `Rules`. The time is in seconds.
+//! `counted` means the game is already in the statistics, e.g.: as a loss at a stalemate.
+//! `penalties` are the score penalties of undone turns.)
+
+use crate::cards::Card;
+use crate::data_files;
+use crate::game_logic::{self, Move, Scoring};
+use crate::game_state::{GameState, Rules};
+use crate::history::History;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SavedGame {
+    pub seed: u64,
+    pub rules: Rules,
+    pub scoring: Scoring,
+    /// Time played so far
+    pub time: Duration,
+    /// Whether the game has already been counted in the statistics
+    pub counted: bool,
+    /// The score penalties of undone turns
+    pub undone_penalties: i32,
+    /// The moves of each turn played since the deal
+    pub turns: Vec<Vec<Move>>,
+}
+
+impl SavedGame {
+    /// Rebuild the game (and its undo history) by replaying the saved turns
+    pub fn restore(&self) -> Result<(GameState, History), String> {
+        let mut game_state = GameState::init(Card::seeded_deck(self.seed));
+        game_state.rules = self.rules;
+        game_logic::face_up_on_columns(&mut game_state);
+
+        let mut history = History::default();
+        for (index, turn) in self.turns.iter().enumerate() {
+            for mv in turn {
+                mv.apply(&mut game_state)
+                    .map_err(|_| format!("turn {}: can't apply move {mv}", index + 1))?;
+            }
+            history.record(turn.clone());
+        }
+        Ok((game_state, history))
+    }
+
+    pub const FILE_NAME: &'static str = "saved_game.txt";
+
+    /// Returns: the saved game, or None if there isn't one
+    pub fn load() -> Result<Option<Self>, String> {
+        data_files::load(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        data_files::write(Self::FILE_NAME, &self.to_string())
+    }
+
+    /// Remove the save file, if any
+    pub fn delete() -> Result<(), String> {
+        data_files::remove(Self::FILE_NAME)
+    }
+}
+
+impl Display for SavedGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        write!(f, "{}", self.rules)?;
+        writeln!(f, "scoring {}", self.scoring.name().to_lowercase())?;
+        writeln!(f, "time {}", self.time.as_secs())?;
+        if self.counted {
+            writeln!(f, "counted")?;
+        }
+        if self.undone_penalties != 0 {
+            writeln!(f, "penalties {}", self.undone_penalties)?;
+        }
+        for turn in &self.turns {
+            let turn: Vec<String> = turn.iter().map(Move::to_string).collect();
+            writeln!(f, "{}", turn.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SavedGame {
+    /// A description of the first error
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seed = None;
+        let mut rules = Rules::default();
+        let mut scoring = Scoring::default();
+        let mut time = Duration::ZERO;
+        let mut counted = false;
+        let mut undone_penalties = 0;
+        let mut turns = vec![];
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: &str| format!("line {}: {message}: {line}", line_index + 1);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("seed ") {
+                seed = Some(value.trim().parse().map_err(|_| error("invalid seed"))?);
+            } else if let Some(result) = rules.parse_line(line) {
+                result.map_err(|message| error(&message))?;
+            } else if let Some(value) = line.strip_prefix("scoring ") {
+                scoring = value.trim().parse().map_err(|_| error("invalid scoring"))?;
+            } else if let Some(value) = line.strip_prefix("time ") {
+                time =
+                    Duration::from_secs(value.trim().parse().map_err(|_| error("invalid time"))?);
+            } else if line == "counted" {
+                counted = true;
+            } else if let Some(value) = line.strip_prefix("penalties ") {
+                undone_penalties = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| error("invalid penalties"))?;
+            } else {
+                let turn = line
+                    .split_whitespace()
+                    .map(|text| {
+                        text.parse()
+                            .map_err(|_| error(&format!("invalid move \"{text}\"")))
+                    })
+                    .collect::<Result<_, _>>()?;
+                turns.push(turn);
+            }
+        }
+
+        Ok(Self {
+            seed: seed.ok_or("missing seed")?,
+            rules,
+            scoring,
+            time,
+            counted,
+            undone_penalties,
+            turns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_game_text() {
+        let saved_game = SavedGame {
+            seed: 42,
+            rules: Rules {
+                draw_count: 3,
+                pass_limit: Some(3),
+                ..Default::default()
+            },
+            scoring: Scoring::Vegas,
+            time: Duration::from_secs(312),
+            counted: true,
+            undone_penalties: -5,
+            turns: vec![
+                vec![Move::Draw { card_count: 3 }],
+                vec![Move::DeckToPile { pile: 0 }, Move::Flip { column: 3 }],
+            ],
+        };
+        let text = saved_game.to_string();
+        assert_eq!(
+            text,
+            "seed 42\ndraw 3\npasses 3\nscoring vegas\ntime 312\ncounted\npenalties -5\ndraw:3\nw>f1 flip:c4\n"
+        );
+        assert_eq!(text.parse(), Ok(saved_game));
+
+        assert_eq!(
+            "seed 1\nc1>c2 oops".parse::<SavedGame>(),
+            Err("line 2: invalid move \"oops\": c1>c2 oops".to_string())
+        );
+    }
+
+    #[test]
+    fn test_restore() {
+        let mut saved_game = SavedGame {
+            seed: 7,
+            rules: Rules::default(),
+            scoring: Scoring::Standard,
+            time: Duration::ZERO,
+            counted: false,
+            undone_penalties: 0,
+            turns: vec![],
+        };
+        let (initial, mut history) = saved_game.restore().unwrap();
+        assert!(!history.undo(&mut initial.clone()));
+
+        let mut game_state = initial.clone();
+        saved_game.turns.push(game_logic::deck_hit(&mut game_state));
+        saved_game.turns.push(game_logic::deck_hit(&mut game_state));
+
+        let (mut restored, mut history) = saved_game.restore().unwrap();
+        assert_eq!(restored, game_state);
+        assert!(history.undo(&mut restored));
+        assert!(history.undo(&mut restored));
+        assert_eq!(restored, initial);
+
+        saved_game
+            .turns
+            .push(vec![Move::PileToColumn { pile: 0, column: 0 }]);
+        assert_eq!(
+            saved_game.restore().unwrap_err(),
+            "turn 3: can't apply move f1>c1"
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use crate::game_logic::MoveError;
 use crate::game_state::{CardCollection, GameState};
+#[cfg(feature = "tui")]
 use std::cmp::{max, min};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -9,10 +11,6 @@ pub enum Selection {
 }
 
 impl Selection {
-    fn new_column(index: usize, card_count: usize) -> Selection {
-        Self::Column { index, card_count }
-    }
-
     /// Is this selection in the same Deck, Column, or Pile as `other`?
     /// (I.e.: are variant and index equal?)
     pub fn same_collection(&self, other: Self) -> bool {
@@ -47,6 +45,14 @@ impl Selection {
             _ => 1,
         }
     }
+}
+
+/// Cursor movement, for the terminal game
+#[cfg(feature = "tui")]
+impl Selection {
+    fn new_column(index: usize, card_count: usize) -> Selection {
+        Self::Column { index, card_count }
+    }
 
     /// for the Left key
     pub fn move_left(&mut self) {
@@ -108,8 +114,11 @@ impl Selection {
 
     pub fn apply_column_selection_rules(&mut self, game_state: &GameState, debug_mode: bool) {
         if let Self::Column { index, card_count } = *self {
+            let Some(column) = game_state.columns.get(index) else {
+                return;
+            };
             // Prevent size zero selection for non-empty column
-            if !game_state.columns[index].0.is_empty() && card_count == 0 {
+            if !column.0.is_empty() && card_count == 0 {
                 *self = Self::Column {
                     index,
                     card_count: 1,
@@ -119,10 +128,10 @@ impl Selection {
 
             let max_count = if debug_mode {
                 // In debug mode, allow selection of face-down cards
-                game_state.columns[index].0.len()
+                column.0.len()
             } else {
                 // Only allow selection of face-up cards
-                game_state.columns[index].face_up_cards()
+                column.face_up_cards()
             };
 
             *self = Self::Column {
@@ -131,22 +140,42 @@ impl Selection {
             }
         }
     }
+}
+
+impl Selection {
+    /// Get the selected card collection (read-only)
+    pub fn collection<'a>(
+        &self,
+        game_state: &'a GameState,
+    ) -> Result<&'a dyn CardCollection, MoveError> {
+        Ok(match self {
+            Self::Deck => &game_state.deck_drawn,
+            Self::Column { index, .. } => game_state
+                .columns
+                .get(*index)
+                .ok_or(MoveError::NoSuchColumn)?,
+            Self::Pile { index } => game_state
+                .card_piles
+                .get(*index)
+                .ok_or(MoveError::NoSuchPile)?,
+        })
+    }
 
     /// Get the selected card collection
     pub fn selected_collection<'a>(
         &'a self,
         game_state: &'a mut GameState,
-    ) -> &mut dyn CardCollection {
-        match self {
+    ) -> Result<&'a mut dyn CardCollection, MoveError> {
+        Ok(match self {
             Self::Deck => &mut game_state.deck_drawn,
             Self::Column { index, .. } => game_state
                 .columns
                 .get_mut(*index)
-                .expect("selected card column should exist"),
+                .ok_or(MoveError::NoSuchColumn)?,
             Self::Pile { index } => game_state
                 .card_piles
                 .get_mut(*index)
-                .expect("selected card pile should exist"),
-        }
+                .ok_or(MoveError::NoSuchPile)?,
+        })
     }
 }
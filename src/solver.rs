@@ -0,0 +1,231 @@
+//! Searches for a winning sequence of moves from a game state.
+
+use crate::game_logic::{self, Move};
+use crate::game_state::GameState;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+use std::vec;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolveResult {
+    /// A winning sequence of moves (including face-up flips)
+    Solved(Vec<Move>),
+    /// Every reachable state was searched: there is no winning sequence
+    Unsolvable,
+    /// The search budget ran out first
+    Unknown,
+}
+
+/// Limits on the work done by one search
+#[derive(Debug, Copy, Clone)]
+pub struct Budget {
+    /// Maximum number of distinct game states to visit
+    pub max_states: usize,
+    pub max_time: Duration,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            max_states: 2_000_000,
+            max_time: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Search for a way to win from `game_state`, using its rules
+pub fn solve(game_state: &GameState, budget: Budget) -> SolveResult {
+    let mut solver = Solver {
+        visited: HashSet::new(),
+        budget,
+        start: Instant::now(),
+        out_of_budget: false,
+    };
+
+    let mut game_state = game_state.clone();
+    let mut path = game_logic::auto_flip(&mut game_state);
+
+    if solver.search(&mut game_state, &mut path) {
+        SolveResult::Solved(path)
+    } else if solver.out_of_budget {
+        SolveResult::Unknown
+    } else {
+        SolveResult::Unsolvable
+    }
+}
+
+struct Solver {
+    /// Hashes of all visited game states (a transposition table)
+    visited: HashSet<u64>,
+    budget: Budget,
+    start: Instant,
+    out_of_budget: bool,
+}
+
+impl Solver {
+    /// Depth-first search: on success, `path` ends with the winning moves.
+    /// (This keeps its own stack of levels instead of recursing, since a search can go
+    /// thousands of moves deep)
+    fn search(&mut self, game_state: &mut GameState, path: &mut Vec<Move>) -> bool {
+        // For each level of the search: the moves still to try, and the path length there
+        let mut levels: Vec<(vec::IntoIter<Move>, usize)> = vec![];
+        loop {
+            if game_logic::victory(game_state) {
+                return true;
+            }
+            if self.visited.insert(state_hash(game_state)) {
+                if self.visited.len() > self.budget.max_states
+                    || (self.visited.len().is_multiple_of(1024)
+                        && self.start.elapsed() > self.budget.max_time)
+                {
+                    self.out_of_budget = true;
+                    return false;
+                }
+                levels.push((candidate_moves(game_state).into_iter(), path.len()));
+            }
+
+            // Back up to the deepest level with a move left to try, then make that move
+            loop {
+                let Some((moves, path_len)) = levels.last_mut() else {
+                    return false;
+                };
+                for mv in path.drain(*path_len..).rev() {
+                    mv.revert(game_state).expect("applied move should revert");
+                }
+                if let Some(mv) = moves.next() {
+                    mv.apply(game_state).expect("legal move should apply");
+                    path.push(mv);
+                    path.extend(game_logic::auto_flip(game_state));
+                    break;
+                }
+                levels.pop();
+            }
+        }
+    }
+}
+
+/// A hash of a game position (as far as the rules are concerned)
+pub(crate) fn state_hash(game_state: &GameState) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    game_state.deck.hash(&mut hasher);
+    game_state.deck_drawn.hash(&mut hasher);
+    game_state.columns.hash(&mut hasher);
+    game_state.card_piles.hash(&mut hasher);
+    // With unlimited passes, the stock's pass number doesn't matter
+    // (hashing it would make the search recycle forever)
+    if game_state.rules.pass_limit.is_some() {
+        game_state.recycle_count.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Moves to search, most promising first.
+/// This skips only moves that can't lead anywhere new, so that the search stays exhaustive.
+fn candidate_moves(game_state: &GameState) -> Vec<Move> {
+    let legal_moves = game_logic::legal_moves(game_state);
+
+    // A safe move to a foundation never needs to be undone: try only that
+    if let Some(&mv) = legal_moves
+        .iter()
+        .find(|&&mv| game_logic::safe_to_foundation(mv, game_state))
+    {
+        return vec![mv];
+    }
+
+    let mut moves = game_logic::hints(game_state);
+    for mv in legal_moves {
+        if !moves.contains(&mv) && !moves_whole_column_to_empty(mv, game_state) {
+            moves.push(mv);
+        }
+    }
+    moves
+}
+
+/// Moving a column's only cards to an empty column just swaps the columns
+fn moves_whole_column_to_empty(mv: Move, game_state: &GameState) -> bool {
+    match mv {
+        Move::ColumnToColumn {
+            from,
+            to,
+            card_count,
+        } => card_count == game_state.columns[from].0.len() && game_state.columns[to].0.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+
+    fn replay(game_state: &GameState, moves: &[Move]) -> GameState {
+        let mut a = game_state.clone();
+        for mv in moves {
+            game_logic::play(*mv, &mut a).unwrap();
+        }
+        a
+    }
+
+    #[test]
+    fn test_solve_almost_victory() {
+        let a = GameState::almost_victory();
+        assert_eq!(
+            solve(&a, Budget::default()),
+            SolveResult::Solved(vec![Move::ColumnToPile { column: 0, pile: 0 }])
+        );
+    }
+
+    #[test]
+    fn test_solve_ordered_deck() {
+        let a = GameState::init(Card::ordered_deck());
+        let SolveResult::Solved(moves) = solve(&a, Budget::default()) else {
+            panic!("ordered deck should be solvable");
+        };
+        assert!(game_logic::victory(&replay(&a, &moves)));
+    }
+
+    #[test]
+    fn test_solve_without_auto_flip() {
+        // Face-down cards have to be turned up with moves of their own
+        let mut a = GameState::init(Card::ordered_deck());
+        a.rules.auto_flip = false;
+        let SolveResult::Solved(moves) = solve(&a, Budget::default()) else {
+            panic!("ordered deck should be solvable");
+        };
+        assert!(moves.contains(&Move::Flip { column: 6 }));
+        assert!(game_logic::victory(&replay(&a, &moves)));
+    }
+
+    #[test]
+    fn test_solve_unsolvable() {
+        // Twos of Hearts through 8, with no Aces or empty columns: no moves at all
+        let a: GameState = "c1: 2H\nc2: 3H\nc3: 4H\nc4: 5H\nc5: 6H\nc6: 7H\nc7: 8H"
+            .parse()
+            .unwrap();
+        assert_eq!(solve(&a, Budget::default()), SolveResult::Unsolvable);
+    }
+
+    #[test]
+    fn test_solve_out_of_budget() {
+        let a = GameState::init(Card::ordered_deck());
+        let budget = Budget {
+            max_states: 10,
+            ..Default::default()
+        };
+        assert_eq!(solve(&a, budget), SolveResult::Unknown);
+    }
+
+    #[test]
+    fn test_solve_deep_search() {
+        // This win is over 150 moves long:
+        // too deep for a small stack, if each move took a stack frame
+        let a = GameState::init(Card::seeded_deck(1));
+        let search = std::thread::Builder::new()
+            .stack_size(32 * 1024)
+            .spawn(move || solve(&a, Budget::default()))
+            .unwrap();
+        assert!(matches!(search.join().unwrap(), SolveResult::Solved(_)));
+    }
+}
@@ -0,0 +1,356 @@
+//! Win/loss statistics for each draw count, kept between runs.
+//!
+//! Text format:
+//! ```text
+//! [draw 1]
+//! wins 3
+//! losses 2
+//! streak 1
+//! best_streak 2
+//! best_time 323
+//! best_score 4210
+//! ```
+//! (Times are in seconds. Unknown keys are ignored.)
+
+use crate::data_files;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct ModeStats {
+    pub wins: u32,
+    pub losses: u32,
+    /// Number of wins since the last loss
+    pub streak: u32,
+    pub best_streak: u32,
+    pub best_time: Option<Duration>,
+    /// Best score of a won game (if it was scored)
+    pub best_score: Option<i32>,
+}
+
+impl ModeStats {
+    /// Percentage of games won, if any were played
+    pub fn win_rate(&self) -> Option<u32> {
+        let played = self.wins + self.losses;
+        (played > 0).then(|| (self.wins * 100 + played / 2) / played)
+    }
+}
+
+/// Statistics for each draw count.
+/// (Games with other differences in their rules are counted together)
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Stats(BTreeMap<usize, ModeStats>);
+
+impl Stats {
+    pub fn mode(&self, draw_count: usize) -> ModeStats {
+        self.0.get(&draw_count).copied().unwrap_or_default()
+    }
+
+    fn mode_mut(&mut self, draw_count: usize) -> &mut ModeStats {
+        self.0.entry(draw_count).or_default()
+    }
+
+    pub fn record_win(&mut self, draw_count: usize, time: Duration, score: Option<i32>) {
+        let stats = self.mode_mut(draw_count);
+        stats.wins += 1;
+        stats.streak += 1;
+        stats.best_streak = stats.best_streak.max(stats.streak);
+        stats.best_time = Some(stats.best_time.map_or(time, |best| best.min(time)));
+        stats.best_score = stats.best_score.max(score);
+    }
+
+    pub fn record_loss(&mut self, draw_count: usize) {
+        let stats = self.mode_mut(draw_count);
+        stats.losses += 1;
+        stats.streak = 0;
+    }
+
+    /// The statistics as a table, for display: Draw One and Draw Three,
+    /// then any other draw counts with recorded games (in blocks of two, to keep it narrow)
+    pub fn table(&self) -> String {
+        let mut draw_counts = vec![1, 3];
+        draw_counts.extend(
+            self.0
+                .iter()
+                .filter(|&(count, stats)| ![1, 3].contains(count) && stats.wins + stats.losses > 0)
+                .map(|(&count, _)| count),
+        );
+        let blocks: Vec<String> = draw_counts
+            .chunks(2)
+            .map(|draw_counts| self.table_block(draw_counts))
+            .collect();
+        blocks.join("\n\n")
+    }
+
+    /// A table of statistics for (up to two) draw counts
+    fn table_block(&self, draw_counts: &[usize]) -> String {
+        let values = |value: fn(&ModeStats) -> String| -> Vec<String> {
+            draw_counts
+                .iter()
+                .map(|&count| value(&self.mode(count)))
+                .collect()
+        };
+        let rows = [
+            ("Won", values(|stats| stats.wins.to_string())),
+            ("Lost", values(|stats| stats.losses.to_string())),
+            (
+                "Win rate",
+                values(|stats| {
+                    stats
+                        .win_rate()
+                        .map_or("--".to_string(), |rate| format!("{rate}%"))
+                }),
+            ),
+            (
+                "Streak (best)",
+                values(|stats| format!("{} ({})", stats.streak, stats.best_streak)),
+            ),
+            (
+                "Best time",
+                values(|stats| stats.best_time.map_or("--".to_string(), format_time)),
+            ),
+            (
+                "Best score",
+                values(|stats| stats.best_score.map_or("--".to_string(), |s| s.to_string())),
+            ),
+        ];
+        let names = draw_counts
+            .iter()
+            .map(|&count| match count {
+                1 => "Draw One".to_string(),
+                3 => "Draw Three".to_string(),
+                _ => format!("Draw {count}"),
+            })
+            .collect();
+
+        let mut lines = vec![];
+        for (label, values) in [("Statistics", names)].into_iter().chain(rows) {
+            let mut line = format!("{label:<14}");
+            for (value, width) in values.iter().zip([10, 12]) {
+                line += &format!("{value:>width$}");
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    pub const FILE_NAME: &'static str = "stats.txt";
+
+    /// Returns: the saved statistics, or empty ones if there aren't any
+    pub fn load() -> Result<Self, String> {
+        Ok(data_files::load(Self::FILE_NAME)?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        data_files::write(Self::FILE_NAME, &self.to_string())
+    }
+
+    /// Load the statistics, change them, and save them again
+    pub fn update(change: impl FnOnce(&mut Self)) -> Result<(), String> {
+        let mut stats = Self::load()?;
+        change(&mut stats);
+        stats.save()
+    }
+}
+
+/// Dollars won or lost over all Vegas games played with the cumulative bankroll on.
+/// (Text format: "bankroll -104")
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Bankroll(pub i32);
+
+impl Bankroll {
+    pub const FILE_NAME: &'static str = "bankroll.txt";
+
+    /// Returns: the saved bankroll, or $0 if there isn't one
+    pub fn load() -> Result<Self, String> {
+        Ok(data_files::load(Self::FILE_NAME)?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        data_files::write(Self::FILE_NAME, &self.to_string())
+    }
+}
+
+impl Display for Bankroll {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "bankroll {}", self.0)
+    }
+}
+
+impl FromStr for Bankroll {
+    /// A description of the error
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .strip_prefix("bankroll ")
+            .and_then(|value| value.trim().parse().ok())
+            .map(Self)
+            .ok_or_else(|| format!("invalid bankroll: {}", s.trim()))
+    }
+}
+
+/// A duration as minutes and seconds, e.g.: "5:23"
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (draw_count, stats) in &self.0 {
+            writeln!(f, "[draw {draw_count}]")?;
+            writeln!(f, "wins {}", stats.wins)?;
+            writeln!(f, "losses {}", stats.losses)?;
+            writeln!(f, "streak {}", stats.streak)?;
+            writeln!(f, "best_streak {}", stats.best_streak)?;
+            if let Some(best_time) = stats.best_time {
+                writeln!(f, "best_time {}", best_time.as_secs())?;
+            }
+            if let Some(best_score) = stats.best_score {
+                writeln!(f, "best_score {best_score}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Stats {
+    /// A description of the first error
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parsed = Self::default();
+        let mut draw_count = None;
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: &str| format!("line {}: {message}: {line}", line_index + 1);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(value) = line
+                .strip_prefix("[draw ")
+                .and_then(|s| s.strip_suffix(']'))
+            {
+                draw_count = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&count| count > 0)
+                        .ok_or_else(|| error("draw count should be at least 1"))?,
+                );
+                continue;
+            }
+
+            let (key, value) = line.split_once(' ').ok_or_else(|| error("missing value"))?;
+            let stats = parsed.mode_mut(draw_count.ok_or_else(|| error("missing [draw N] line"))?);
+            let value: i64 = value.trim().parse().map_err(|_| error("invalid number"))?;
+            let count = || u32::try_from(value).map_err(|_| error("invalid count"));
+            match key {
+                "wins" => stats.wins = count()?,
+                "losses" => stats.losses = count()?,
+                "streak" => stats.streak = count()?,
+                "best_streak" => stats.best_streak = count()?,
+                "best_time" => stats.best_time = Some(Duration::from_secs(count()?.into())),
+                "best_score" => {
+                    stats.best_score =
+                        Some(i32::try_from(value).map_err(|_| error("invalid score"))?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut stats = Stats::default();
+        stats.record_win(1, Duration::from_secs(300), Some(900));
+        stats.record_win(1, Duration::from_secs(200), Some(800));
+        stats.record_loss(1);
+        stats.record_win(1, Duration::from_secs(250), None);
+        stats.record_loss(3);
+
+        assert_eq!(
+            stats.mode(1),
+            ModeStats {
+                wins: 3,
+                losses: 1,
+                streak: 1,
+                best_streak: 2,
+                best_time: Some(Duration::from_secs(200)),
+                best_score: Some(900),
+            }
+        );
+        assert_eq!(stats.mode(1).win_rate(), Some(75));
+        assert_eq!(stats.mode(3).win_rate(), Some(0));
+        assert_eq!(stats.mode(2).win_rate(), None);
+    }
+
+    #[test]
+    fn test_stats_text() {
+        let mut stats = Stats::default();
+        stats.record_win(3, Duration::from_secs(323), Some(4210));
+        stats.record_loss(1);
+
+        let text = stats.to_string();
+        assert_eq!(text.parse(), Ok(stats));
+        assert!(text.ends_with(
+            "[draw 3]\nwins 1\nlosses 0\nstreak 1\nbest_streak 1\nbest_time 323\nbest_score 4210\n"
+        ));
+
+        assert_eq!(
+            "wins 1".parse::<Stats>(),
+            Err("line 1: missing [draw N] line: wins 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bankroll_text() {
+        let bankroll = Bankroll(-104);
+        assert_eq!(bankroll.to_string(), "bankroll -104\n");
+        assert_eq!(bankroll.to_string().parse(), Ok(bankroll));
+        assert_eq!(
+            "-104".parse::<Bankroll>(),
+            Err("invalid bankroll: -104".to_string())
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let mut stats = Stats::default();
+        stats.record_win(1, Duration::from_secs(323), None);
+        let table = stats.table();
+        assert!(table.lines().all(|line| line.len() <= 36));
+        assert!(table.contains("\nWin rate            100%          --\n"));
+        assert!(table.contains("\nStreak (best)      1 (1)       0 (0)\n"));
+        assert!(table.ends_with(
+            "\nBest time           5:23          --\nBest score            --          --"
+        ));
+
+        // Other draw counts get their own block, once they have games
+        stats.mode_mut(2);
+        assert_eq!(stats.table(), table);
+        stats.record_loss(2);
+        stats.record_win(5, Duration::from_secs(100), None);
+        stats.record_loss(7);
+        let table = stats.table();
+        assert!(table.lines().all(|line| line.len() <= 36));
+        assert!(table.contains(
+            "--\n\nStatistics        Draw 2      Draw 5\nWon                    0           1\n"
+        ));
+        assert!(table.contains(
+            "\n\nStatistics        Draw 7\nWon                    0\nLost                   1\n"
+        ));
+    }
+}
@@ -1,104 +1,377 @@
 use crate::cards::Card;
-use crate::draw::Draw;
-use crate::game_logic;
-use crate::game_state::{GameMode, GameState};
+use crate::cli::Args;
+use crate::clock::Clock;
+use crate::draw::{Backend, Draw, TermionBackend};
+use crate::game_logic::{self, Move, MoveError, Scoring, StalemateResult};
+use crate::game_state::{CardState, GameState, Rules};
+use crate::history::History;
+use crate::input::{Event, Key};
+use crate::replay::Replay;
+use crate::save::SavedGame;
 use crate::selection::Selection;
-use std::io::stdin;
-use termion::event::Key;
-use termion::input::TermRead;
+use crate::solver::{self, Budget, SolveResult};
+use crate::stats::{Bankroll, Stats};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
 
-pub struct Ui {
-    /// The deck used to seed the current game (if any)
-    game_deck: Option<Vec<Card>>,
+pub struct Ui<B: Backend = TermionBackend> {
+    /// The seed of the current game's deck (if any)
+    game_seed: Option<u64>,
+    /// The seed for the next new game (random if None)
+    next_seed: Option<u64>,
+    /// Undo/redo history for the current game
+    history: History,
+    /// Moves applied so far in the current turn
+    turn_moves: Vec<Move>,
+    /// Score penalties of the turns that were undone (which still count)
+    undone_penalties: i32,
+    /// The displayed hint, as (index, count) in the current hints
+    hint: Option<(usize, usize)>,
+    /// Message for the context help line, until the next key press
+    message: Option<String>,
+    /// A recorded game to play back, with the delay between moves
+    replay: Option<(Replay, Duration)>,
+    /// A saved game to resume
+    saved_game: Option<SavedGame>,
+    /// The last position checked for a stalemate (as its hash), and the result
+    stalemate_check: Option<(u64, StalemateResult)>,
+    /// Time played in the current game
+    clock: Clock,
+    /// Scoring for new games
+    scoring: Scoring,
+    /// Scoring for the current game
+    game_scoring: Scoring,
+    /// Rules for new games, apart from the draw count (chosen with each new game).
+    /// (Vegas scoring has its own pass limit)
+    rules: Rules,
+    /// Make safe moves to the foundations automatically
+    auto_play: bool,
+    /// The cumulative Vegas bankroll, if it's on
+    bankroll: Option<Bankroll>,
+    /// Whether the current game still has to be counted in the statistics
+    /// (as a win, or as a loss if it's abandoned)
+    counts_in_stats: bool,
+    /// Input events, e.g.: read from the terminal on other threads
+    /// (so that the game clock can tick between key presses)
+    events: Receiver<Event>,
     ui_state: UiState,
-    draw: Draw,
+    draw: Draw<B>,
 }
 
 enum UiState {
     StartScreen,
-    NewGame(GameMode),
+    /// A new game, with this draw count
+    NewGame(usize),
+    Replay,
+    ResumeGame,
     RestartGame,
     Game,
+    /// No more moves worth making
+    Stalemate,
     Victory,
     Quit,
 }
 
-impl Ui {
-    pub fn new() -> Self {
-        Self {
-            game_deck: None,
-            ui_state: UiState::StartScreen,
-            draw: Draw::new(),
+impl<B: Backend> Ui<B> {
+    /// `events`: the input to play with (see `input`)
+    pub fn new(args: &Args, events: Receiver<Event>, draw: Draw<B>) -> Self {
+        // Skip the start screen if the command line chose a game
+        let ui_state = match (args.draw_count, args.seed) {
+            (None, None) => UiState::StartScreen,
+            (draw_count, _) => UiState::NewGame(draw_count.unwrap_or(1)),
+        };
+        let mut ui = Self {
+            game_seed: None,
+            next_seed: args.seed,
+            history: History::default(),
+            turn_moves: vec![],
+            undone_penalties: 0,
+            hint: None,
+            message: None,
+            replay: None,
+            saved_game: None,
+            stalemate_check: None,
+            clock: Clock::default(),
+            counts_in_stats: false,
+            scoring: args.scoring,
+            game_scoring: args.scoring,
+            rules: args.rules,
+            auto_play: args.auto_play,
+            bankroll: None,
+            events,
+            ui_state,
+            draw,
+        };
+        if args.bankroll {
+            ui.load_bankroll();
         }
+        ui
     }
+
+    /// Wait for the next key press, redrawing the screen with `redraw` if it's resized.
+    /// (Ctrl-c if there's no more input)
+    fn next_key(&mut self, redraw: impl Fn(&mut Self)) -> Key {
+        loop {
+            match self.events.recv() {
+                Ok(Event::Key(key)) => return key,
+                Ok(Event::Resize) => {
+                    self.draw.resize();
+                    redraw(self);
+                }
+                Err(_) => return Key::Ctrl('c'),
+            }
+        }
+    }
+
+    /// Run a screen with the game clock paused
+    fn paused<T>(&mut self, screen: impl FnOnce(&mut Self) -> T) -> T {
+        self.clock.pause();
+        let result = screen(self);
+        self.clock.resume();
+        result
+    }
+
     pub fn reset_for_new_game(&mut self) {
         self.draw.cursor = Selection::Deck;
         self.draw.selected = None;
         self.draw.debug_message.clear();
         self.draw.context_help_message.clear();
+        self.history.clear();
+        self.turn_moves.clear();
+        self.undone_penalties = 0;
+        self.draw.move_count = 0;
+        self.hint = None;
+        self.message = None;
+        self.stalemate_check = None;
+        self.draw.game_seed = self.game_seed;
+        self.clock = Clock::starting_at(Duration::ZERO);
+        self.counts_in_stats = true;
+    }
+
+    /// The score for the moves played so far (if the game is scored)
+    fn score(&self, game_state: &GameState) -> Option<i32> {
+        let score = game_logic::score(
+            self.game_scoring,
+            game_state.draw_count(),
+            self.history.turns().iter().flatten(),
+        )?;
+        Some(match self.game_scoring {
+            // (The score never goes below zero)
+            Scoring::Standard => (score + self.undone_penalties).max(0),
+            _ => score + self.undone_penalties,
+        })
     }
 
-    fn move_cards(from: Selection, to: Selection, game_state: &mut GameState) -> Result<(), ()> {
-        if from.same_collection(to) {
-            return Err(());
+    /// The score penalties of the latest turn
+    fn last_turn_penalties(&self, game_state: &GameState) -> i32 {
+        game_logic::penalties(
+            self.game_scoring,
+            game_state.draw_count(),
+            self.history.turns().last().into_iter().flatten(),
+        )
+    }
+
+    /// Set the displayed score
+    fn show_score(&mut self, score: Option<i32>) {
+        self.draw.score = score.map(|score| match (self.game_scoring, self.bankroll) {
+            (Scoring::Vegas, Some(Bankroll(bankroll))) => {
+                format!("Bank: {}", Scoring::Vegas.format(bankroll + score))
+            }
+            (scoring, _) => format!("Score: {}", scoring.format(score)),
+        });
+    }
+
+    /// Turn on the cumulative bankroll, loading it from disk
+    fn load_bankroll(&mut self) {
+        match Bankroll::load() {
+            Ok(bankroll) => self.bankroll = Some(bankroll),
+            Err(error) => self.message = Some(format!("Bankroll off: {error}")),
+        }
+    }
+
+    /// Add a finished game's result to the cumulative bankroll, if it's on
+    fn settle_bankroll(&mut self, score: Option<i32>) -> Result<(), String> {
+        if let (Scoring::Vegas, Some(bankroll), Some(score)) =
+            (self.game_scoring, &mut self.bankroll, score)
+        {
+            bankroll.0 += score;
+            bankroll
+                .save()
+                .map_err(|error| format!("Bankroll not saved: {error}"))?;
+        }
+        Ok(())
+    }
+
+    /// Count the current game as lost, if it hasn't been won
+    fn abandon_game(&mut self, game_state: &GameState) -> Result<(), String> {
+        if !std::mem::take(&mut self.counts_in_stats) {
+            return Ok(());
         }
+        self.settle_bankroll(self.score(game_state))?;
+        Stats::update(|stats| stats.record_loss(game_state.draw_count()))
+            .map_err(|error| format!("Statistics not saved: {error}"))
+    }
 
-        let cards = from
-            .selected_collection(game_state)
-            .take(from.card_count())?;
+    /// Check for a stalemate, unless the position hasn't changed since the last check
+    fn stalemate(&mut self, game_state: &GameState) -> StalemateResult {
+        let hash = solver::state_hash(game_state);
+        match self.stalemate_check {
+            Some((checked, result)) if checked == hash => result,
+            _ => {
+                let result = game_logic::stalemate(game_state);
+                self.stalemate_check = Some((hash, result));
+                result
+            }
+        }
+    }
 
-        to.selected_collection(game_state).receive(cards)?;
+    /// Apply a move and add it to the current turn
+    fn play(&mut self, mv: Move, game_state: &mut GameState) -> Result<(), MoveError> {
+        mv.apply(game_state)?;
+        self.turn_moves.push(mv);
         Ok(())
     }
 
+    fn move_cards(
+        &mut self,
+        from: Selection,
+        to: Selection,
+        game_state: &mut GameState,
+    ) -> Result<(), MoveError> {
+        let mv = Move::from_selections(from, to).ok_or(MoveError::NoSuchMove)?;
+        self.play(mv, game_state)
+    }
+
     fn cards_action(&mut self, game_state: &mut GameState) {
         if let (Some(from), to) = (self.draw.selected, self.draw.cursor) {
             self.draw.selected = None;
 
-            if game_logic::valid_move(from, to, game_state).is_ok() {
-                match Self::move_cards(from, to, game_state) {
+            match game_logic::valid_move(from, to, game_state) {
+                Ok(()) => match self.move_cards(from, to, game_state) {
                     Ok(_) => self.draw.debug_message = "move OK".to_string(),
                     Err(_) => self.draw.debug_message = "move attempt failed".to_string(),
-                }
-            } else {
-                self.draw.debug_message = "invalid move".to_string();
+                },
+                Err(error) => self.message = Some(error.to_string()),
             }
         } else if self.draw.cursor.card_count() > 0 {
             self.draw.selected = Some(self.draw.cursor);
         }
     }
 
-    fn move_to_pile(from: Selection, game_state: &mut GameState) {
-        for i in 0..4 {
+    /// Move to whichever foundation takes the card, or explain why none does
+    fn move_to_pile(&mut self, from: Selection, game_state: &mut GameState) {
+        let mut error = MoveError::WrongSuit;
+        for i in 0..GameState::CARD_PILES_COUNT {
             let to = Selection::Pile { index: i };
-            if game_logic::valid_move(from, to, game_state).is_ok() {
-                let _ = Self::move_cards(from, to, game_state);
-                break;
+            match game_logic::valid_move(from, to, game_state) {
+                Ok(()) => {
+                    let _ = self.move_cards(from, to, game_state);
+                    return;
+                }
+                // Report why the card's own foundation didn't take it
+                Err(MoveError::WrongSuit) => {}
+                Err(e) => error = e,
             }
         }
+        self.message = Some(error.to_string());
     }
 
     fn enter_key_action(&mut self, game_state: &mut GameState) {
         if let Selection::Deck = self.draw.cursor {
             if let Some(Selection::Deck) = self.draw.selected {
-                Self::move_to_pile(Selection::Deck, game_state);
+                self.move_to_pile(Selection::Deck, game_state);
             } else {
-                game_state.deck_hit();
+                let moves = game_logic::deck_hit(game_state);
+                let stuck = moves.is_empty() && !game_state.deck_drawn.is_empty();
+                if let (true, Some(pass_limit)) = (stuck, game_state.rules.pass_limit) {
+                    self.message = Some(format!(
+                        "No more passes through the stock (limit: {pass_limit})"
+                    ));
+                }
+                self.turn_moves.extend(moves);
             }
         } else if let Selection::Column { index, .. } = self.draw.cursor {
+            let flip = Move::Flip { column: index };
+            if game_logic::valid(flip, game_state).is_ok() {
+                self.play(flip, game_state)
+                    .expect("valid flip should apply");
+                self.draw.selected = None;
+                return;
+            }
             self.draw.cursor = Selection::Column {
                 index,
                 card_count: 1,
             };
-            Self::move_to_pile(self.draw.cursor, game_state);
+            self.move_to_pile(self.draw.cursor, game_state);
         }
         self.draw.selected = None;
     }
 
+    /// Move the remaining cards to the foundations, one at a time
+    fn auto_complete(&mut self, game_state: &mut GameState) {
+        const STEP_DELAY: Duration = Duration::from_millis(80);
+        self.draw.selected = None;
+        while let Some(mv) = game_logic::auto_complete_move(game_state) {
+            self.play(mv, game_state)
+                .expect("auto-complete move should apply");
+            self.apply_column_selection_rules(game_state);
+            self.draw.display_game_state(game_state);
+            thread::sleep(STEP_DELAY);
+        }
+    }
+
+    fn undo(&mut self, game_state: &mut GameState) {
+        self.draw.selected = None;
+        let penalties = self.last_turn_penalties(game_state);
+        if self.history.undo(game_state) {
+            self.undone_penalties += penalties;
+        } else {
+            self.draw.debug_message = "nothing to undo".to_string();
+        }
+    }
+
+    fn redo(&mut self, game_state: &mut GameState) {
+        self.draw.selected = None;
+        if self.history.redo(game_state) {
+            // (The redone turn's penalties are in the history again)
+            self.undone_penalties -= self.last_turn_penalties(game_state);
+        } else {
+            self.draw.debug_message = "nothing to redo".to_string();
+        }
+    }
+
+    /// Show the next suggested move, using the cursor and selection
+    fn hint_action(&mut self, game_state: &GameState) {
+        let hints = game_logic::hints(game_state);
+        if hints.is_empty() {
+            self.hint = None;
+            self.message = Some("No moves to suggest".to_string());
+            return;
+        }
+
+        let index = match self.hint {
+            Some((index, _)) => (index + 1) % hints.len(),
+            None => 0,
+        };
+        self.hint = Some((index, hints.len()));
+        self.message = Some(format!("Hint {}/{}", index + 1, hints.len()));
+
+        if let Some((from, to)) = hints[index].selections() {
+            self.draw.selected = Some(from);
+            self.draw.cursor = to;
+        } else {
+            self.draw.selected = None;
+            self.draw.cursor = Selection::Deck;
+        }
+    }
+
+    /// Move cards without checking the game rules.
+    /// (Undoing this restores moved face-down cards as face-up.)
     fn debug_unchecked_cards_action(&mut self, game_state: &mut GameState) {
         if let Some(selected) = self.draw.selected {
             self.draw.selected = None;
-            let _ = Self::move_cards(selected, self.draw.cursor, game_state);
+            let _ = self.move_cards(selected, self.draw.cursor, game_state);
         } else {
             self.draw.selected = Some(self.draw.cursor)
         }
@@ -121,8 +394,24 @@ impl Ui {
         }
     }
 
-    fn set_context_help_message(&mut self) {
+    fn set_context_help_message(&mut self, game_state: &GameState) {
+        if let Some(message) = &self.message {
+            self.draw.context_help_message = message.clone();
+            return;
+        }
+        if game_logic::can_auto_complete(game_state) {
+            self.draw.context_help_message = "a: Auto-complete".to_string();
+            return;
+        }
         self.draw.context_help_message = match (self.draw.cursor, self.draw.selected) {
+            (Selection::Column { index, .. }, _)
+                if matches!(
+                    game_state.columns[index].0.last(),
+                    Some((_, CardState::FaceDown))
+                ) =>
+            {
+                "Enter: Turn card up"
+            }
             (Selection::Column { .. }, _) | (Selection::Deck, Some(Selection::Deck)) => {
                 "Enter: Try to Move to Stack"
             }
@@ -135,23 +424,65 @@ impl Ui {
     /// Actions run on each user turn
     /// Returns: true IFF UiState has changed
     fn turn_actions(&mut self, game_state: &mut GameState) -> bool {
-        // Ensure a face-up card at the end of each column
-        game_logic::face_up_on_columns(game_state);
-        // Hit if the deck has cards and the drawn deck is empty
-        // game_state.auto_hit(); [disabled; should remove permanently?]
+        let moved = !self.turn_moves.is_empty();
+        // Turn up uncovered cards (if the rules say so),
+        // and move safe cards to the foundations (if that's on)
+        loop {
+            let moves = game_logic::auto_flip(game_state);
+            self.turn_moves.extend(moves);
+            if !self.auto_play {
+                break;
+            }
+            let Some(mv) = game_logic::safe_foundation_move(game_state) else {
+                break;
+            };
+            self.play(mv, game_state).expect("safe move should apply");
+        }
+        // Record this turn's moves for undo
+        // (Undone moves still count as moves played)
+        self.draw.move_count += self.turn_moves.len();
+        self.history.record(std::mem::take(&mut self.turn_moves));
+        self.show_score(self.score(game_state));
+        self.draw.game_time = self.clock.elapsed();
         // Fix column selections, if needed
         self.apply_column_selection_rules(game_state);
         // Update context help line
-        self.set_context_help_message();
+        self.set_context_help_message(game_state);
 
         // (Any other automatic state changes can go here too)
 
         if game_logic::victory(game_state) {
             self.draw.debug_message = "Victory".to_string();
+            self.clock.pause();
+            let time = self.clock.elapsed();
+            self.draw.game_time = time;
+            let score = self
+                .score(game_state)
+                .map(|score| score + game_logic::time_bonus(self.game_scoring, time));
+            self.show_score(score);
+            if std::mem::take(&mut self.counts_in_stats) {
+                // (Only Standard scores are comparable between games)
+                let best_score = score.filter(|_| self.game_scoring == Scoring::Standard);
+                let result = self.settle_bankroll(score).and_then(|()| {
+                    Stats::update(|stats| {
+                        stats.record_win(game_state.draw_count(), time, best_score)
+                    })
+                    .map_err(|error| format!("Statistics not saved: {error}"))
+                });
+                if let Err(error) = result {
+                    self.draw.debug_message = error;
+                }
+            }
             self.ui_state = UiState::Victory;
             return true;
         }
 
+        // (Only checked after a move, so that the player can keep playing after undo or Esc)
+        if moved && self.stalemate(game_state) == StalemateResult::Stuck {
+            self.ui_state = UiState::Stalemate;
+            return true;
+        }
+
         self.draw.display_game_state(game_state);
         false
     }
@@ -161,9 +492,26 @@ impl Ui {
             return;
         }
 
-        let stdin = stdin();
-        for c in stdin.keys() {
-            match c.unwrap() {
+        loop {
+            let key = match self.events.recv_timeout(self.clock.until_next_second()) {
+                Ok(Event::Key(key)) => key,
+                Ok(Event::Resize) => {
+                    self.draw.resize();
+                    self.draw.display_game_state(game_state);
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    self.draw.game_time = self.clock.elapsed();
+                    self.draw.display_clock();
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => Key::Ctrl('c'),
+            };
+            if key != Key::Char('\t') {
+                self.hint = None;
+            }
+            self.message = None;
+            match key {
                 Key::Left => self.draw.cursor.move_left(),
                 Key::Right => self.draw.cursor.move_right(),
                 Key::Up => self.draw.cursor.select_up(),
@@ -176,14 +524,16 @@ impl Ui {
                     self.debug_unchecked_cards_action(game_state)
                 }
                 Key::Char('x') => self.draw.selected = None,
+                Key::Char('a') if game_logic::can_auto_complete(game_state) => {
+                    self.auto_complete(game_state)
+                }
+                Key::Char('u') => self.undo(game_state),
+                Key::Char('r') => self.redo(game_state),
+                Key::Char('\t') => self.hint_action(game_state),
                 Key::Char('z') if self.draw.debug_mode => self.debug_check_valid(game_state),
                 Key::Char('d') => self.draw.debug_mode = !self.draw.debug_mode,
-                Key::Char('h') => self.run_help(game_state),
-                Key::Esc => {
-                    if self.run_game_menu(game_state) {
-                        break;
-                    }
-                }
+                Key::Char('h') => self.paused(|s| s.run_help(game_state)),
+                Key::Esc if self.paused(|s| s.run_game_menu(game_state)) => break,
                 Key::Ctrl('c') => {
                     self.ui_state = UiState::Quit;
                     break;
@@ -197,16 +547,53 @@ impl Ui {
     }
 
     fn run_start_screen(&mut self) {
-        self.draw.display_start_screen();
-        let stdin = stdin();
-        for c in stdin.keys() {
-            match c.unwrap() {
+        // Offer the saved game, if there is one
+        let saved_game_line = match (self.message.take(), SavedGame::load()) {
+            (Some(message), _) => Some(message),
+            (None, Ok(saved_game)) => {
+                self.saved_game = saved_game;
+                self.saved_game
+                    .as_ref()
+                    .map(|_| "c: Continue saved game".to_string())
+            }
+            (None, Err(_)) => Some("(Saved game is unreadable)".to_string()),
+        };
+        let display = |s: &mut Self| {
+            s.draw
+                .display_start_screen(s.next_seed, saved_game_line.as_deref())
+        };
+
+        display(self);
+        loop {
+            match self.next_key(display) {
+                Key::Char('s') => {
+                    self.run_seed_entry();
+                    if let UiState::Quit = self.ui_state {
+                        break;
+                    }
+                    display(self);
+                }
+                Key::Char('t') => {
+                    self.run_stats();
+                    display(self);
+                }
+                Key::Char('o') => {
+                    self.run_options();
+                    if let UiState::Quit = self.ui_state {
+                        break;
+                    }
+                    display(self);
+                }
+                Key::Char('c') if self.saved_game.is_some() => {
+                    self.ui_state = UiState::ResumeGame;
+                    break;
+                }
                 Key::Char('1') => {
-                    self.ui_state = UiState::NewGame(GameMode::DrawOne);
+                    self.ui_state = UiState::NewGame(1);
                     break;
                 }
                 Key::Char('3') => {
-                    self.ui_state = UiState::NewGame(GameMode::DrawThree);
+                    self.ui_state = UiState::NewGame(3);
                     break;
                 }
                 Key::Esc | Key::Ctrl('c') => {
@@ -218,24 +605,83 @@ impl Ui {
         }
     }
 
+    /// Edit the seed for the next new game
+    fn run_seed_entry(&mut self) {
+        let mut text = self
+            .next_seed
+            .map(|seed| seed.to_string())
+            .unwrap_or_default();
+        self.draw.display_seed_entry(&text);
+
+        loop {
+            match self.next_key(|s| s.draw.display_seed_entry(&text)) {
+                Key::Char(c) if c.is_ascii_digit() => {
+                    let longer = format!("{text}{c}");
+                    // Ignore digits that would overflow the seed
+                    if longer.parse::<u64>().is_ok() {
+                        text = longer;
+                    }
+                }
+                Key::Backspace => {
+                    text.pop();
+                }
+                Key::Char('\n') => {
+                    self.next_seed = text.parse().ok();
+                    break;
+                }
+                Key::Esc => break,
+                Key::Ctrl('c') => {
+                    self.ui_state = UiState::Quit;
+                    break;
+                }
+                _ => {}
+            }
+            self.draw.display_seed_entry(&text);
+        }
+    }
+
     /// Returns: true IFF UiState has changed
     fn run_game_menu(&mut self, game_state: &mut GameState) -> bool {
         self.draw.display_game_menu(game_state);
-        let stdin = stdin();
-        for c in stdin.keys() {
-            match c.unwrap() {
+        loop {
+            match self.next_key(|s| s.draw.display_game_menu(game_state)) {
                 Key::Char('1') => {
-                    self.ui_state = UiState::NewGame(GameMode::DrawOne);
+                    self.ui_state = UiState::NewGame(1);
                     return true;
                 }
                 Key::Char('3') => {
-                    self.ui_state = UiState::NewGame(GameMode::DrawThree);
+                    self.ui_state = UiState::NewGame(3);
                     return true;
                 }
                 Key::Char('r') => {
                     self.ui_state = UiState::RestartGame;
                     return true;
                 }
+                Key::Char('w') => {
+                    self.draw.draw_text_box("Searching for a win...");
+                    self.draw.flush();
+                    self.message = Some(Self::winnable_message(game_state));
+                    return false;
+                }
+                Key::Char('t') => {
+                    self.run_stats();
+                    self.draw.display_game_menu(game_state);
+                }
+                Key::Char('o') => {
+                    self.run_options();
+                    if let UiState::Quit = self.ui_state {
+                        return true;
+                    }
+                    self.show_score(self.score(game_state));
+                    self.draw.display_game_menu(game_state);
+                }
+                Key::Char('s') => {
+                    self.message = Some(match self.saved_game(game_state).save() {
+                        Ok(()) => "Game saved".to_string(),
+                        Err(error) => format!("Save failed: {error}"),
+                    });
+                    return false;
+                }
                 Key::Char('q') | Key::Ctrl('c') => {
                     self.ui_state = UiState::Quit;
                     return true;
@@ -246,17 +692,69 @@ impl Ui {
                 _ => {}
             }
         }
-        false
+    }
+
+    /// Describe whether the game can still be won
+    fn winnable_message(game_state: &GameState) -> String {
+        let budget = Budget {
+            max_time: Duration::from_secs(3),
+            ..Default::default()
+        };
+        match solver::solve(game_state, budget) {
+            SolveResult::Solved(moves) => {
+                let count = moves
+                    .iter()
+                    .filter(|mv| !matches!(mv, Move::Flip { .. }))
+                    .count();
+                format!("Winnable: found a win in {count} moves")
+            }
+            SolveResult::Unsolvable => "Not winnable from here".to_string(),
+            SolveResult::Unknown => "Unknown: no win found in time".to_string(),
+        }
+    }
+
+    fn run_stalemate(&mut self, game_state: &mut GameState) {
+        self.clock.pause();
+        // (The loss is only recorded once the player leaves the game: they can still undo)
+        let counts = self.counts_in_stats;
+        self.draw.display_stalemate(game_state, counts);
+
+        loop {
+            match self.next_key(|s| s.draw.display_stalemate(game_state, counts)) {
+                Key::Char('u') => {
+                    self.undo(game_state);
+                    break;
+                }
+                Key::Char('r') => {
+                    self.ui_state = UiState::RestartGame;
+                    return;
+                }
+                Key::Char('n') => {
+                    self.ui_state = UiState::NewGame(game_state.draw_count());
+                    return;
+                }
+                Key::Esc => break,
+                Key::Char('q') | Key::Ctrl('c') => {
+                    if let Err(error) = self.abandon_game(game_state) {
+                        self.draw.debug_message = error;
+                    }
+                    self.ui_state = UiState::Quit;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.clock.resume();
+        self.ui_state = UiState::Game;
     }
 
     fn run_victory(&mut self, game_state: &mut GameState) {
         self.draw.display_victory(game_state);
 
-        let stdin = stdin();
-        for c in stdin.keys() {
-            match c.unwrap() {
+        loop {
+            match self.next_key(|s| s.draw.display_victory(game_state)) {
                 Key::Char('y') => {
-                    self.ui_state = UiState::NewGame(game_state.game_mode);
+                    self.ui_state = UiState::NewGame(game_state.draw_count());
                     break;
                 }
                 Key::Char('n') | Key::Esc | Key::Ctrl('c') => {
@@ -268,30 +766,198 @@ impl Ui {
         }
     }
 
-    pub fn run_new_game(&mut self, game_state: &mut GameState, game_mode: GameMode) {
-        let game_deck = Card::shuffled_deck();
-        self.game_deck = Some(game_deck.clone());
-        *game_state = GameState::init(game_deck);
-        game_state.game_mode = game_mode;
+    pub fn run_new_game(&mut self, game_state: &mut GameState, draw_count: usize) {
+        let stats_result = self.abandon_game(game_state);
+        let seed = self.next_seed.take().unwrap_or_else(rand::random);
+        self.game_seed = Some(seed);
+        *game_state = GameState::init(Card::seeded_deck(seed));
+        game_state.rules = Rules {
+            draw_count,
+            pass_limit: self
+                .scoring
+                .pass_limit(draw_count)
+                .or(self.rules.pass_limit),
+            ..self.rules
+        };
+        game_logic::face_up_on_columns(game_state);
         self.reset_for_new_game();
+        self.game_scoring = self.scoring;
+        self.message = stats_result.err();
         self.ui_state = UiState::Game;
     }
 
     pub fn run_restart_game(&mut self, game_state: &mut GameState) {
-        let game_mode = game_state.game_mode;
-        *game_state = GameState::init(
-            self.game_deck
-                .clone()
-                .expect("deck for current game should exist"),
-        );
-        game_state.game_mode = game_mode;
+        let stats_result = self.abandon_game(game_state);
+        let rules = game_state.rules;
+        let seed = self.game_seed.expect("seed for current game should exist");
+        *game_state = GameState::init(Card::seeded_deck(seed));
+        game_state.rules = rules;
+        game_logic::face_up_on_columns(game_state);
         self.reset_for_new_game();
+        self.message = stats_result.err();
         self.ui_state = UiState::Game;
     }
 
+    fn run_options(&mut self) {
+        let display = |s: &mut Self| {
+            s.draw.display_options(
+                s.scoring,
+                s.rules,
+                s.auto_play,
+                s.bankroll.map(|Bankroll(bankroll)| bankroll),
+            )
+        };
+        display(self);
+        loop {
+            match self.next_key(display) {
+                Key::Char('s') => self.scoring = self.scoring.next(),
+                Key::Char('p') => {
+                    self.rules.pass_limit = match self.rules.pass_limit {
+                        None => Some(1),
+                        Some(pass_limit @ 1..=2) => Some(pass_limit + 1),
+                        Some(_) => None,
+                    }
+                }
+                Key::Char('e') => self.rules.empty_column = self.rules.empty_column.toggled(),
+                Key::Char('f') => {
+                    self.rules.foundation_to_tableau = !self.rules.foundation_to_tableau
+                }
+                Key::Char('a') => self.rules.auto_flip = !self.rules.auto_flip,
+                Key::Char('m') => self.auto_play = !self.auto_play,
+                Key::Char('b') if self.bankroll.is_some() => self.bankroll = None,
+                Key::Char('b') => self.load_bankroll(),
+                Key::Char('r') if self.bankroll.is_some() => {
+                    let bankroll = Bankroll::default();
+                    if let Err(error) = bankroll.save() {
+                        self.message = Some(format!("Bankroll not saved: {error}"));
+                    }
+                    self.bankroll = Some(bankroll);
+                }
+                Key::Esc => break,
+                Key::Ctrl('c') => {
+                    self.ui_state = UiState::Quit;
+                    break;
+                }
+                _ => {}
+            }
+            display(self);
+        }
+    }
+
+    /// Show the statistics until a key is pressed
+    fn run_stats(&mut self) {
+        let text = match Stats::load() {
+            Ok(stats) => stats.table(),
+            Err(error) => format!("Can't read statistics:\n{error}"),
+        };
+        self.draw.display_stats(&text);
+        self.next_key(|s| s.draw.display_stats(&text));
+    }
+
+    /// The current game, for saving
+    fn saved_game(&self, game_state: &GameState) -> SavedGame {
+        SavedGame {
+            seed: self.game_seed.expect("seed for current game should exist"),
+            rules: game_state.rules,
+            scoring: self.game_scoring,
+            time: self.clock.elapsed(),
+            counted: !self.counts_in_stats,
+            undone_penalties: self.undone_penalties,
+            turns: self.history.turns().to_vec(),
+        }
+    }
+
+    /// Save the current game, if it's still in progress, or else remove any old save
+    fn save_on_quit(&self, game_state: &GameState) -> Result<(), String> {
+        if self.game_seed.is_none() {
+            // No game was played
+            Ok(())
+        } else if game_logic::victory(game_state) {
+            SavedGame::delete()
+        } else {
+            self.saved_game(game_state).save()
+        }
+    }
+
+    /// Start by resuming `saved_game`, instead of the current UiState
+    pub fn start_resume(&mut self, saved_game: SavedGame) {
+        self.saved_game = Some(saved_game);
+        self.ui_state = UiState::ResumeGame;
+    }
+
+    fn run_resume_game(&mut self, game_state: &mut GameState) {
+        let saved_game = self.saved_game.take().expect("saved game should exist");
+        match saved_game.restore() {
+            Ok((restored, history)) => {
+                self.game_seed = Some(saved_game.seed);
+                *game_state = restored;
+                self.reset_for_new_game();
+                // (e.g.: a game lost at a stalemate, then saved)
+                self.counts_in_stats = !saved_game.counted;
+                self.game_scoring = saved_game.scoring;
+                self.clock = Clock::starting_at(saved_game.time);
+                self.history = history;
+                self.undone_penalties = saved_game.undone_penalties;
+                self.draw.move_count = saved_game.turns.iter().map(Vec::len).sum();
+                self.ui_state = UiState::Game;
+            }
+            Err(error) => {
+                self.message = Some(format!("Can't resume: {error}"));
+                self.ui_state = UiState::StartScreen;
+            }
+        }
+    }
+
+    /// Start by playing back `replay`, instead of the current UiState
+    pub fn start_replay(&mut self, replay: Replay, delay: Duration) {
+        self.replay = Some((replay, delay));
+        self.ui_state = UiState::Replay;
+    }
+
+    /// Play back the recorded game, then continue it as a normal game
+    fn run_replay(&mut self, game_state: &mut GameState) {
+        let (replay, delay) = self.replay.take().expect("replay should exist");
+        self.game_seed = Some(replay.seed);
+        *game_state = replay.initial_state();
+        game_logic::face_up_on_columns(game_state);
+        self.reset_for_new_game();
+        // (A played-back game isn't the player's own)
+        self.counts_in_stats = false;
+        self.game_scoring = Scoring::Off;
+        self.ui_state = UiState::Game;
+
+        // (The recorded moves include any that auto-play made, so it would get ahead of them)
+        let auto_play = std::mem::replace(&mut self.auto_play, false);
+        self.play_back(replay.moves, delay, game_state);
+        self.auto_play = auto_play;
+    }
+
+    /// Play `moves` one at a time, stopping at the first invalid one
+    fn play_back(&mut self, moves: Vec<Move>, delay: Duration, game_state: &mut GameState) {
+        if self.turn_actions(game_state) {
+            return;
+        }
+        for mv in moves {
+            // Face-up flips are automatic here
+            if matches!(mv, Move::Flip { .. }) && game_logic::valid(mv, game_state).is_err() {
+                continue;
+            }
+            thread::sleep(delay);
+            if game_logic::valid(mv, game_state).is_err() {
+                self.message = Some(format!("Replay stopped: invalid move {mv}"));
+                return;
+            }
+            self.play(mv, game_state).expect("valid move should apply");
+            if self.turn_actions(game_state) {
+                return;
+            }
+        }
+        self.message = Some("Replay finished".to_string());
+    }
+
     pub fn run_help(&mut self, game_state: &mut GameState) {
         self.draw.display_help(game_state);
-        stdin().keys().next();
+        self.next_key(|s| s.draw.display_help(game_state));
     }
 
     pub fn run(&mut self, game_state: &mut GameState) {
@@ -300,18 +966,25 @@ impl Ui {
         loop {
             match self.ui_state {
                 UiState::StartScreen => self.run_start_screen(),
-                UiState::NewGame(game_mode) => self.run_new_game(game_state, game_mode),
+                UiState::NewGame(draw_count) => self.run_new_game(game_state, draw_count),
                 UiState::RestartGame => self.run_restart_game(game_state),
+                UiState::Replay => self.run_replay(game_state),
+                UiState::ResumeGame => self.run_resume_game(game_state),
                 UiState::Game => self.run_game(game_state),
+                UiState::Stalemate => self.run_stalemate(game_state),
                 UiState::Victory => self.run_victory(game_state),
                 UiState::Quit => break,
             }
         }
 
+        let save_result = self.save_on_quit(game_state);
+
         self.draw.restore_terminal();
-        self.draw
-            .draw_text(1, 1, "please send bug reports via IRC or ham radio");
-        self.draw.draw_text(1, 1, "");
+        print!("please send bug reports via IRC or ham radio\r\n");
+        // (still in raw mode: no automatic carriage return)
+        if let Err(error) = save_result {
+            eprint!("solitext: can't save game: {error}\r\n");
+        }
     }
 }
 
@@ -319,6 +992,156 @@ impl Ui {
 mod tests {
     use super::*;
     use crate::cards::Card;
+    use crate::data_files;
+    use crate::draw::{Grid, Theme};
+    use crate::input;
+    use std::path::PathBuf;
+
+    /// A Ui that plays `events` and draws to a Grid,
+    /// with its data files in a new directory (see `remove_data_dir`)
+    fn scripted_ui(args: &Args, events: &str, name: &str) -> Ui<Grid> {
+        let dir = std::env::temp_dir().join(format!("solitext-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        data_files::TEST_DIR.with(|test_dir| *test_dir.borrow_mut() = Some(dir));
+
+        let events = input::scripted_events(input::parse_events(events).unwrap());
+        let draw = Draw::with_backend(Grid::new(80, 24), Theme::default(), true);
+        Ui::new(args, events, draw)
+    }
+
+    fn remove_data_dir() {
+        let dir: Option<PathBuf> = data_files::TEST_DIR.with(|test_dir| test_dir.take());
+        let dir = dir.unwrap();
+        // (It's only made when something is saved)
+        if dir.exists() {
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_scripted_session() {
+        // Options, seed entry, a new game with one hit, help, then the menu's save and quit
+        let events = "o m Esc  s 4 2 Enter  1  Enter  h Resize x  Esc q";
+        let mut ui = scripted_ui(&Args::default(), events, "session");
+        let mut game_state = GameState::init(Card::ordered_deck());
+        ui.run(&mut game_state);
+
+        assert!(ui.auto_play);
+        assert_eq!(ui.game_seed, Some(42));
+        assert_eq!(game_state.deck_drawn.len(), 1);
+        let saved_game = SavedGame::load().unwrap().unwrap();
+        assert_eq!(saved_game.seed, 42);
+        // (Auto-play moved some Aces up first)
+        let moves = saved_game.turns.concat();
+        assert_eq!(moves.last(), Some(&Move::Draw { card_count: 1 }));
+        assert!(matches!(moves[0], Move::ColumnToPile { .. }));
+        remove_data_dir();
+    }
+
+    #[test]
+    fn test_scripted_victory() {
+        // Move the last card to its foundation, then decline another game
+        let mut ui = scripted_ui(&Args::default(), "Right Enter n", "victory");
+        ui.ui_state = UiState::Game;
+        ui.game_seed = Some(1);
+        ui.counts_in_stats = true;
+        let mut game_state = GameState::almost_victory();
+        ui.run(&mut game_state);
+
+        assert!(game_logic::victory(&game_state));
+        assert_eq!(Stats::load().unwrap().mode(1).wins, 1);
+        assert_eq!(SavedGame::load(), Ok(None));
+        remove_data_dir();
+    }
+
+    #[test]
+    fn test_scripted_stalemate_then_resume() {
+        let stuck = "stock: 9H TH\nc1: 2H\nc2: 3H\nc3: 4H\nc4: 5H\nc5: 6H\nc6: 7H\nc7: 8H";
+        // Hit the stock (nothing in it helps), then keep playing from the stalemate screen
+        let mut ui = scripted_ui(&Args::default(), "Enter Esc", "stalemate");
+        ui.ui_state = UiState::Game;
+        ui.game_seed = Some(1);
+        ui.counts_in_stats = true;
+        ui.run(&mut stuck.parse().unwrap());
+        assert!(ui.counts_in_stats);
+        assert_eq!(Stats::load().unwrap().mode(1).losses, 0);
+
+        // This time, quit at the stalemate screen
+        let mut ui = scripted_ui(&Args::default(), "Enter q", "stalemate");
+        ui.ui_state = UiState::Game;
+        ui.game_seed = Some(1);
+        ui.counts_in_stats = true;
+        let mut game_state: GameState = stuck.parse().unwrap();
+        ui.run(&mut game_state);
+
+        assert_eq!(Stats::load().unwrap().mode(1).losses, 1);
+        let saved_game = SavedGame::load().unwrap().unwrap();
+        assert!(saved_game.counted);
+
+        // Resumed, the game isn't counted again
+        let events = input::scripted_events([]);
+        let draw = Draw::with_backend(Grid::new(80, 24), Theme::default(), true);
+        let mut ui = Ui::new(&Args::default(), events, draw);
+        ui.start_resume(saved_game);
+        ui.run(&mut GameState::default());
+        assert!(!ui.counts_in_stats);
+        assert!(SavedGame::load().unwrap().unwrap().counted);
+        remove_data_dir();
+    }
+
+    #[test]
+    fn test_scripted_replay_with_auto_play() {
+        // A recorded win, played back with auto-play on (then quit at the victory screen)
+        let mut replay = Replay {
+            seed: 1,
+            rules: Rules::default(),
+            moves: vec![],
+        };
+        let SolveResult::Solved(moves) = solver::solve(&replay.initial_state(), Budget::default())
+        else {
+            panic!("seed 1 should be solvable");
+        };
+        replay.moves = moves;
+        let args = Args {
+            auto_play: true,
+            ..Default::default()
+        };
+        let mut ui = scripted_ui(&args, "n", "replay");
+        ui.start_replay(replay, Duration::ZERO);
+        let mut game_state = GameState::default();
+        ui.run(&mut game_state);
+
+        assert!(game_logic::victory(&game_state));
+        assert!(ui.auto_play);
+        remove_data_dir();
+    }
+
+    #[test]
+    fn test_undone_penalties() {
+        let mut ui = scripted_ui(&Args::default(), "", "penalties");
+        ui.game_scoring = Scoring::Standard;
+        let mut game_state: GameState = "draw 1\nwaste: 9H TH\nc1: 2H\nc2: 3H\nf1: AH"
+            .parse()
+            .unwrap();
+        for mv in [Move::ColumnToPile { column: 0, pile: 0 }, Move::Recycle] {
+            ui.play(mv, &mut game_state).unwrap();
+            ui.turn_actions(&mut game_state);
+        }
+        assert_eq!(ui.score(&game_state), Some(0));
+
+        // Undoing the recycle doesn't take back its penalty...
+        ui.undo(&mut game_state);
+        assert_eq!(ui.score(&game_state), Some(0));
+        // ...and redoing it doesn't count it twice
+        ui.redo(&mut game_state);
+        ui.undo(&mut game_state);
+        assert_eq!(ui.score(&game_state), Some(0));
+        ui.undo(&mut game_state);
+        assert_eq!(ui.undone_penalties, -100);
+        // (Undone moves still count as moves played)
+        assert_eq!(ui.draw.move_count, 2);
+        remove_data_dir();
+    }
 
     #[test]
     fn test_same_collection() {